@@ -0,0 +1,11 @@
+//! Parses one translation unit for `Isolation::Subprocess`, see `serve_parse_request`.
+
+use libclang_wrapper::source::serve_parse_request;
+use std::process;
+
+fn main() {
+    if let Err(error) = serve_parse_request() {
+        eprintln!("parse_worker: {}", error);
+        process::exit(2);
+    }
+}
@@ -0,0 +1,353 @@
+use std::path::{Component, Path, PathBuf};
+
+type Adjuster = Box<dyn Fn(Vec<String>, &str) -> Vec<String> + Send + Sync>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArgumentInsertPosition {
+    Begin,
+    End,
+}
+
+/// Pipeline of transformations applied to compilation database arguments before they are
+/// handed to libclang, the equivalent of clang tooling's `ArgumentsAdjuster`.
+/// Adjusters run in the order they were added.
+#[derive(Default)]
+pub struct ArgumentsAdjuster {
+    adjusters: Vec<Adjuster>,
+}
+
+// launchers which prefix the real compiler in build systems' commands
+const COMPILER_LAUNCHERS: [&str; 3] = ["ccache", "sccache", "distcc"];
+
+// flags whose value is the next argument, so that value is never an input file
+const SEPARATE_VALUE_FLAGS: [&str; 24] = [
+    "-o",
+    "-x",
+    "-I",
+    "-D",
+    "-U",
+    "-MF",
+    "-MT",
+    "-MQ",
+    "-MJ",
+    "-include",
+    "-include-pch",
+    "-imacros",
+    "-isystem",
+    "-iquote",
+    "-idirafter",
+    "-isysroot",
+    "--sysroot",
+    "-target",
+    "-arch",
+    "-Xclang",
+    "-Xpreprocessor",
+    "-Xlinker",
+    "-L",
+    "-l",
+];
+
+// flags which start with `-o` without being a joined `-o<output>`, matched as prefixes
+const NOT_OUTPUT_FLAGS: [&str; 3] = ["-objc", "-object", "-order"];
+
+const DEPENDENCY_FLAGS: [&str; 7] = ["-M", "-MM", "-MD", "-MMD", "-MG", "-MP", "-MV"];
+
+const DEPENDENCY_VALUE_FLAGS: [&str; 4] = ["-MF", "-MT", "-MQ", "-MJ"];
+
+// GCC options libclang either rejects or does not know, matched as prefixes
+const GCC_ONLY_FLAGS: [&str; 30] = [
+    "-fconserve-stack",
+    "-fno-var-tracking-assignments",
+    "-fno-tree-",
+    "-ftree-",
+    "-fmerge-constants",
+    "-fno-allow-store-data-races",
+    "-fstack-usage",
+    "-fno-ipa-",
+    "-fipa-",
+    "-fno-partial-inlining",
+    "-flto-partition=",
+    "-fno-semantic-interposition",
+    "-mno-fp-ret-in-387",
+    "-mpreferred-stack-boundary=",
+    "-mindirect-branch=",
+    "-mrecord-mcount",
+    "-Wmaybe-uninitialized",
+    "-Wno-maybe-uninitialized",
+    "-Wlogical-op",
+    "-Wduplicated-cond",
+    "-Wduplicated-branches",
+    "-Wuseless-cast",
+    "-Wnoexcept",
+    "-Wstrict-null-sentinel",
+    "-Wno-psabi",
+    "-Wformat-truncation",
+    "-Wformat-overflow",
+    "-Wstringop-",
+    "-Wno-stringop-",
+    "-Wclass-memaccess",
+];
+
+// `-o<output>`, but not a known flag which happens to start with `-o`
+fn is_joined_output(arg: &str) -> bool {
+    arg.len() > 2
+        && arg.starts_with("-o")
+        && !NOT_OUTPUT_FLAGS.iter().any(|flag| arg.starts_with(flag))
+}
+
+fn is_source_file(arg: &str, file: &str) -> bool {
+    let relative: PathBuf = Path::new(arg)
+        .components()
+        .skip_while(|component| matches!(component, Component::CurDir | Component::ParentDir))
+        .collect();
+    !relative.as_os_str().is_empty() && Path::new(file).ends_with(&relative)
+}
+
+fn is_c_standard(args: &[String]) -> bool {
+    args.iter()
+        .any(|arg| arg.starts_with("-std=c") && !arg.starts_with("-std=c++"))
+        || args
+            .iter()
+            .any(|arg| arg.starts_with("-std=gnu") && !arg.starts_with("-std=gnu++"))
+}
+
+fn language_for(file: &str, args: &[String]) -> Option<&'static str> {
+    let extension = Path::new(file).extension()?.to_str()?;
+    let language = match extension {
+        "c" => "c",
+        "C" | "cc" | "cp" | "cpp" | "cxx" | "c++" | "CPP" => "c++",
+        "h" if is_c_standard(args) => "c-header",
+        "h" | "H" | "hh" | "hp" | "hpp" | "hxx" | "h++" | "inl" | "tcc" => "c++-header",
+        "m" => "objective-c",
+        "mm" | "M" => "objective-c++",
+        "cu" => "cuda",
+        _ => return None,
+    };
+    Some(language)
+}
+
+impl ArgumentsAdjuster {
+    pub fn new() -> ArgumentsAdjuster {
+        ArgumentsAdjuster { adjusters: vec![] }
+    }
+
+    /// Pipeline which turns a compiler invocation from a compilation database into arguments
+    /// libclang accepts.
+    pub fn standard() -> ArgumentsAdjuster {
+        let mut result = ArgumentsAdjuster::new();
+        result
+            .strip_compiler_path()
+            .strip_output()
+            .strip_dependency_flags()
+            .strip_gcc_only_flags();
+        result
+    }
+
+    /// Removes the compiler executable, and a `ccache`-like launcher in front of it.
+    pub fn strip_compiler_path(&mut self) -> &mut ArgumentsAdjuster {
+        self.custom(|args, _| {
+            let launcher = args
+                .first()
+                .and_then(|arg| Path::new(arg).file_name())
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| COMPILER_LAUNCHERS.contains(&name));
+            let executables = args
+                .iter()
+                .take(if launcher { 2 } else { 1 })
+                .take_while(|arg| !arg.starts_with('-'))
+                .count();
+            args.into_iter().skip(executables).collect()
+        })
+    }
+
+    /// Removes `-c`, `-o <output>` and the source file itself, which libclang receives separately.
+    pub fn strip_output(&mut self) -> &mut ArgumentsAdjuster {
+        self.custom(|args, file| {
+            let mut result = Vec::with_capacity(args.len());
+            let mut args = args.into_iter();
+            while let Some(arg) = args.next() {
+                if arg == "-c" {
+                    continue;
+                } else if arg == "-o" {
+                    args.next();
+                } else if is_joined_output(&arg) {
+                    continue;
+                } else if SEPARATE_VALUE_FLAGS.contains(&arg.as_str()) {
+                    result.push(arg);
+                    if let Some(value) = args.next() {
+                        result.push(value);
+                    }
+                } else if arg.starts_with('-') || !is_source_file(&arg, file) {
+                    result.push(arg);
+                }
+            }
+            result
+        })
+    }
+
+    /// Removes `-M`-family flags which make the compiler write dependency files.
+    pub fn strip_dependency_flags(&mut self) -> &mut ArgumentsAdjuster {
+        self.custom(|args, _| {
+            let mut result = Vec::with_capacity(args.len());
+            let mut args = args.into_iter();
+            while let Some(arg) = args.next() {
+                if DEPENDENCY_FLAGS.contains(&arg.as_str()) {
+                    continue;
+                } else if DEPENDENCY_VALUE_FLAGS.contains(&arg.as_str()) {
+                    args.next();
+                } else if !DEPENDENCY_VALUE_FLAGS
+                    .iter()
+                    .any(|flag| arg.starts_with(flag))
+                {
+                    result.push(arg);
+                }
+            }
+            result
+        })
+    }
+
+    /// Removes GCC-specific optimization, code generation and warning flags libclang does not know.
+    pub fn strip_gcc_only_flags(&mut self) -> &mut ArgumentsAdjuster {
+        self.custom(|args, _| {
+            args.into_iter()
+                .filter(|arg| !GCC_ONLY_FLAGS.iter().any(|flag| arg.starts_with(flag)))
+                .collect()
+        })
+    }
+
+    /// Adds `extra_args` at `position`, e.g. `-Wno-unknown-warning-option` or extra defines.
+    pub fn insert_args(
+        &mut self,
+        extra_args: Vec<String>,
+        position: ArgumentInsertPosition,
+    ) -> &mut ArgumentsAdjuster {
+        self.custom(move |args, _| match position {
+            ArgumentInsertPosition::Begin => extra_args.iter().cloned().chain(args).collect(),
+            ArgumentInsertPosition::End => {
+                args.into_iter().chain(extra_args.iter().cloned()).collect()
+            }
+        })
+    }
+
+    /// Adds `-x <language>` derived from the file extension unless the language is already given.
+    /// Plain `.h` headers are treated as C++ unless a C `-std=` is present.
+    pub fn language_from_extension(&mut self) -> &mut ArgumentsAdjuster {
+        self.custom(|mut args, file| {
+            if args.iter().any(|arg| arg == "-x" || arg.starts_with("-x")) {
+                return args;
+            }
+            if let Some(language) = language_for(file, &args) {
+                args.push("-x".to_owned());
+                args.push(language.to_owned());
+            }
+            args
+        })
+    }
+
+    /// Adds an arbitrary transformation receiving the arguments and the main file name.
+    pub fn custom<F>(&mut self, adjuster: F) -> &mut ArgumentsAdjuster
+    where
+        F: Fn(Vec<String>, &str) -> Vec<String> + Send + Sync + 'static,
+    {
+        self.adjusters.push(Box::new(adjuster));
+        self
+    }
+
+    pub fn adjust(&self, args: Vec<String>, file: &str) -> Vec<String> {
+        self.adjusters
+            .iter()
+            .fold(args, |args, adjuster| adjuster(args, file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_owned(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
+    #[test]
+    fn test_standard_pipeline() {
+        let adjuster = ArgumentsAdjuster::standard();
+        assert_eq!(
+            adjuster.adjust(
+                to_owned(&[
+                    "/usr/bin/ccache",
+                    "/usr/bin/clang++-10",
+                    "-Iinclude",
+                    "-MD",
+                    "-MT",
+                    "src/main.cpp.o",
+                    "-MFsrc/main.cpp.o.d",
+                    "-o",
+                    "src/main.cpp.o",
+                    "-Wlogical-op",
+                    "-fconserve-stack",
+                    "-std=c++14",
+                    "-c",
+                    "../src/main.cpp",
+                ]),
+                "/project/src/main.cpp"
+            ),
+            to_owned(&["-Iinclude", "-std=c++14"])
+        );
+    }
+
+    #[test]
+    fn test_strip_output_keeps_flag_values() {
+        let mut adjuster = ArgumentsAdjuster::new();
+        adjuster.strip_output();
+        assert_eq!(
+            adjuster.adjust(
+                to_owned(&["-include", "main.cpp", "-omain.o", "main.cpp"]),
+                "/project/main.cpp"
+            ),
+            to_owned(&["-include", "main.cpp"])
+        );
+    }
+
+    #[test]
+    fn test_strip_output_keeps_flags_starting_with_o() {
+        let mut adjuster = ArgumentsAdjuster::new();
+        adjuster.strip_output();
+        assert_eq!(
+            adjuster.adjust(
+                to_owned(&[
+                    "-objcmt-migrate-literals",
+                    "-object_path_lto",
+                    "-ordered",
+                    "-omain.o"
+                ]),
+                "/project/main.cpp"
+            ),
+            to_owned(&["-objcmt-migrate-literals", "-object_path_lto", "-ordered"])
+        );
+    }
+
+    #[test]
+    fn test_insert_args_and_language() {
+        let mut adjuster = ArgumentsAdjuster::new();
+        adjuster
+            .insert_args(to_owned(&["-DBEGIN"]), ArgumentInsertPosition::Begin)
+            .insert_args(to_owned(&["-DEND"]), ArgumentInsertPosition::End)
+            .language_from_extension();
+        assert_eq!(
+            adjuster.adjust(to_owned(&["-std=c++17"]), "include/foo.h"),
+            to_owned(&["-DBEGIN", "-std=c++17", "-DEND", "-x", "c++-header"])
+        );
+        assert_eq!(
+            adjuster.adjust(to_owned(&["-std=c11"]), "include/foo.h"),
+            to_owned(&["-DBEGIN", "-std=c11", "-DEND", "-x", "c-header"])
+        );
+        assert_eq!(
+            adjuster.adjust(to_owned(&["-x", "c"]), "main.cpp"),
+            to_owned(&["-DBEGIN", "-x", "c", "-DEND"])
+        );
+        assert_eq!(
+            adjuster.adjust(vec![], "main.txt"),
+            to_owned(&["-DBEGIN", "-DEND"])
+        );
+    }
+}
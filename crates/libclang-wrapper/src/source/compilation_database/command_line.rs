@@ -0,0 +1,100 @@
+use std::error::Error;
+
+/// Splits `command` into arguments the way a POSIX shell would, honoring single and double
+/// quotes and backslash escapes. Variables, globs and other expansions are not performed.
+pub fn split(command: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut args = vec![];
+    let mut current = String::new();
+    // distinguishes an empty quoted argument (`""`) from no argument at all
+    let mut in_argument = false;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_argument = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(format!("unterminated `'` in `{}`", command).into()),
+                    }
+                }
+            }
+            '"' => {
+                in_argument = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ '$') | Some(c @ '`') | Some(c @ '"') | Some(c @ '\\') => {
+                                current.push(c)
+                            }
+                            Some('\n') => (),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => {
+                                return Err(format!("unterminated `\"` in `{}`", command).into())
+                            }
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(format!("unterminated `\"` in `{}`", command).into()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => (),
+                Some(c) => {
+                    in_argument = true;
+                    current.push(c);
+                }
+                None => return Err(format!("trailing `\\` in `{}`", command).into()),
+            },
+            c if c.is_whitespace() => {
+                if in_argument {
+                    args.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            c => {
+                in_argument = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_argument {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            split("  clang++ -c  main.cpp\t-o main.o ").unwrap(),
+            vec!["clang++", "-c", "main.cpp", "-o", "main.o"]
+        );
+        assert_eq!(
+            split(r#"clang++ -DNAME="a b" '-DQUOTED="x"' -I/Program\ Files/include"#).unwrap(),
+            vec![
+                "clang++",
+                "-DNAME=a b",
+                r#"-DQUOTED="x""#,
+                "-I/Program Files/include"
+            ]
+        );
+        assert_eq!(
+            split(r#"cc -DA=\"b\" "\$HOME \a" '' """#).unwrap(),
+            vec!["cc", r#"-DA="b""#, r"$HOME \a", "", ""]
+        );
+        assert_eq!(split("cc \\\n -c").unwrap(), vec!["cc", "-c"]);
+        assert!(split("cc trailing\\").is_err());
+        assert!(split("cc 'unterminated").is_err());
+        assert!(split("cc \"unterminated").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::source::compilation_database::{
+    absolute, CompilationDatabase, CompilationDatabaseError, FixedCompilationDatabase,
+    JsonCompilationDatabase, Parsed,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// checked in every directory, in this order, like clangd does
+const CANDIDATES: [&str; 3] = [
+    "compile_commands.json",
+    "build/compile_commands.json",
+    "compile_flags.txt",
+];
+
+fn find_in(directory: &Path) -> Option<PathBuf> {
+    CANDIDATES
+        .iter()
+        .map(|candidate| directory.join(candidate))
+        .find(|candidate| candidate.is_file())
+}
+
+fn load(file: &Path) -> Result<Box<dyn CompilationDatabase>, CompilationDatabaseError> {
+    if file
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        Ok(Box::new(JsonCompilationDatabase::new(file)?))
+    } else {
+        Ok(Box::new(FixedCompilationDatabase::from_flags_file(
+            file,
+            &[],
+        )?))
+    }
+}
+
+/// Walks up from `path`, a source file or a directory, to the closest directory with a
+/// `compile_commands.json` (possibly in its `build` subdirectory) or `compile_flags.txt`
+/// and loads it. The database is returned as it is, whatever `path` is: `commands` are the
+/// entries of a `compile_commands.json` and none for a `compile_flags.txt`, which lists no
+/// files. `command_for` and `for_files` give the commands for specific files with either.
+pub fn discover(path: &Path) -> Result<Box<dyn CompilationDatabase>, CompilationDatabaseError> {
+    let path = PathBuf::from(absolute(path));
+    match path.ancestors().find_map(find_in) {
+        Some(file) => load(&file),
+        None => Err(CompilationDatabaseError::new(
+            &path,
+            "no compilation database found",
+        )),
+    }
+}
+
+/// Finds the database for each file on demand by walking up from it, see `discover`.
+/// Databases are loaded once per directory they live in.
+pub struct AutoCompilationDatabase {
+    files: Vec<PathBuf>,
+    databases: Mutex<HashMap<PathBuf, Box<dyn CompilationDatabase>>>,
+}
+
+impl AutoCompilationDatabase {
+    /// `files` are the ones `commands` returns, in this order; `command_for` works for any
+    /// file with a database above it.
+    pub fn new(files: &[&Path]) -> AutoCompilationDatabase {
+        AutoCompilationDatabase {
+            files: files
+                .iter()
+                .map(|file| PathBuf::from(absolute(file)))
+                .collect(),
+            databases: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl CompilationDatabase for AutoCompilationDatabase {
+    /// Returns the commands for the files given on creation which have a database.
+    fn commands(&self) -> Vec<Parsed> {
+        self.files
+            .iter()
+            .filter_map(|file| self.command_for(file))
+            .collect()
+    }
+
+    fn command_for(&self, file: &Path) -> Option<Parsed> {
+        let path = PathBuf::from(absolute(file));
+        let location = path.ancestors().find_map(find_in)?;
+        let mut databases = self.databases.lock().unwrap();
+        if !databases.contains_key(&location) {
+            let database = load(&location).ok()?;
+            databases.insert(location.clone(), database);
+        }
+        databases[&location].command_for(&path)
+    }
+}
@@ -0,0 +1,289 @@
+use crate::source::compilation_database::Parsed;
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::env;
+use std::error::Error;
+use std::path::Path;
+
+/// Pattern matched against the resolved file of a command, both as an absolute path and
+/// relative to the current directory, so `src/**` works when run from the project root.
+/// In a `TraversalFilter` the working directory of the translation unit is used instead.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "PatternSource", into = "PatternSource")]
+pub enum FilePattern {
+    Glob(Pattern),
+    Regex(Regex),
+}
+
+// patterns are serialized as the text they were compiled from
+#[derive(Serialize, Deserialize)]
+enum PatternSource {
+    Glob(String),
+    Regex(String),
+}
+
+impl From<FilePattern> for PatternSource {
+    fn from(pattern: FilePattern) -> Self {
+        match pattern {
+            FilePattern::Glob(pattern) => PatternSource::Glob(pattern.as_str().to_owned()),
+            FilePattern::Regex(regex) => PatternSource::Regex(regex.as_str().to_owned()),
+        }
+    }
+}
+
+impl TryFrom<PatternSource> for FilePattern {
+    type Error = Box<dyn Error>;
+
+    fn try_from(pattern: PatternSource) -> Result<Self, Self::Error> {
+        match pattern {
+            PatternSource::Glob(pattern) => FilePattern::glob(&pattern),
+            PatternSource::Regex(pattern) => FilePattern::regex(&pattern),
+        }
+    }
+}
+
+impl FilePattern {
+    /// `*` does not cross directory boundaries, `**` does.
+    pub fn glob(pattern: &str) -> Result<FilePattern, Box<dyn Error>> {
+        Ok(FilePattern::Glob(Pattern::new(pattern)?))
+    }
+
+    /// Matches anywhere in the path unless anchored.
+    pub fn regex(pattern: &str) -> Result<FilePattern, Box<dyn Error>> {
+        Ok(FilePattern::Regex(Regex::new(pattern)?))
+    }
+
+    fn matches_path(&self, path: &Path) -> bool {
+        match self {
+            FilePattern::Glob(pattern) => pattern.matches_path_with(
+                path,
+                MatchOptions {
+                    case_sensitive: true,
+                    require_literal_separator: true,
+                    require_literal_leading_dot: false,
+                },
+            ),
+            FilePattern::Regex(regex) => regex.is_match(&path.to_string_lossy()),
+        }
+    }
+
+    // `directory` is what the relative form is relative to
+    pub(crate) fn matches(&self, file: &str, directory: &Path) -> bool {
+        let file = Path::new(file);
+        self.matches_path(file)
+            || file
+                .strip_prefix(directory)
+                .is_ok_and(|relative| self.matches_path(relative))
+    }
+}
+
+/// What to do with several commands for the same file, e.g. PIC and non-PIC variants.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeduplicationPolicy {
+    KeepAll,
+    First,
+    Last,
+    /// Keeps the first command and adds `-D` defines of the others for macros it does not
+    /// define, a macro defined differently keeps the value of the first command.
+    UnionOfDefines,
+}
+
+pub struct CommandFilter {
+    include: Vec<FilePattern>,
+    exclude: Vec<FilePattern>,
+    deduplication: DeduplicationPolicy,
+}
+
+impl Default for CommandFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn defines(args: &[String]) -> Vec<String> {
+    let mut result = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "-D" {
+            if let Some(value) = args.next() {
+                result.push(format!("-D{}", value));
+            }
+        } else if arg.starts_with("-D") {
+            result.push(arg.clone());
+        }
+    }
+    result
+}
+
+// `FOO` of `-DFOO=1`
+fn macro_name(define: &str) -> &str {
+    let define = define.trim_start_matches("-D");
+    define.split('=').next().unwrap_or(define)
+}
+
+// a macro `into` already defines keeps its value
+fn merge_defines(into: &mut Parsed, from: &Parsed) {
+    let mut names: Vec<String> = defines(&into.args)
+        .iter()
+        .map(|define| macro_name(define).to_owned())
+        .collect();
+    for define in defines(&from.args) {
+        let name = macro_name(&define).to_owned();
+        if !names.contains(&name) {
+            names.push(name);
+            into.args.push(define);
+        }
+    }
+}
+
+impl CommandFilter {
+    /// Keeps every command, add patterns and a policy to narrow it down.
+    pub fn new() -> CommandFilter {
+        CommandFilter {
+            include: vec![],
+            exclude: vec![],
+            deduplication: DeduplicationPolicy::KeepAll,
+        }
+    }
+
+    /// Once any include pattern is given only files matching one of them are kept.
+    pub fn include(&mut self, pattern: FilePattern) -> &mut CommandFilter {
+        self.include.push(pattern);
+        self
+    }
+
+    /// Excluded files are dropped even if they match an include pattern.
+    pub fn exclude(&mut self, pattern: FilePattern) -> &mut CommandFilter {
+        self.exclude.push(pattern);
+        self
+    }
+
+    pub fn deduplicate(&mut self, policy: DeduplicationPolicy) -> &mut CommandFilter {
+        self.deduplication = policy;
+        self
+    }
+
+    fn accepts(&self, file: &str, directory: &Path) -> bool {
+        (self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| pattern.matches(file, directory)))
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| pattern.matches(file, directory))
+    }
+
+    /// Filters `commands` keeping their order, deduplicated commands take the place of the
+    /// first command for their file.
+    pub fn apply(&self, commands: Vec<Parsed>) -> Vec<Parsed> {
+        let directory = env::current_dir().unwrap_or_default();
+        let commands = commands
+            .into_iter()
+            .filter(|command| self.accepts(&command.file, &directory));
+        if self.deduplication == DeduplicationPolicy::KeepAll {
+            return commands.collect();
+        }
+        let mut result: Vec<Parsed> = vec![];
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for command in commands {
+            match positions.get(&command.file) {
+                None => {
+                    positions.insert(command.file.clone(), result.len());
+                    result.push(command);
+                }
+                Some(&position) => match self.deduplication {
+                    DeduplicationPolicy::Last => result[position] = command,
+                    DeduplicationPolicy::UnionOfDefines => {
+                        merge_defines(&mut result[position], &command)
+                    }
+                    _ => (),
+                },
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(args: &[&str], file: &str) -> Parsed {
+        Parsed {
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+            file: file.to_owned(),
+            directory: "/project/build".to_owned(),
+        }
+    }
+
+    fn commands() -> Vec<Parsed> {
+        vec![
+            command(&["cc", "-DPIC", "-fPIC"], "/project/src/a.c"),
+            command(&["cc", "-DTEST"], "/project/third_party/b.c"),
+            command(&["cc", "-D", "STATIC", "-DPIC"], "/project/src/a.c"),
+            command(&["cc"], "/project/src/generated/c.c"),
+        ]
+    }
+
+    #[test]
+    fn test_include_and_exclude() {
+        let mut filter = CommandFilter::new();
+        filter
+            .include(FilePattern::glob("/project/src/**").unwrap())
+            .exclude(FilePattern::regex("/generated/").unwrap());
+        assert_eq!(
+            filter.apply(commands()),
+            vec![
+                command(&["cc", "-DPIC", "-fPIC"], "/project/src/a.c"),
+                command(&["cc", "-D", "STATIC", "-DPIC"], "/project/src/a.c"),
+            ]
+        );
+        let mut filter = CommandFilter::new();
+        filter.include(FilePattern::glob("/project/*/*.c").unwrap());
+        assert_eq!(filter.apply(commands()).len(), 3);
+        assert!(FilePattern::glob("[").is_err());
+        assert!(FilePattern::regex("(").is_err());
+    }
+
+    #[test]
+    fn test_deduplication() {
+        let mut filter = CommandFilter::new();
+        filter.deduplicate(DeduplicationPolicy::First);
+        assert_eq!(
+            filter.apply(commands()),
+            vec![
+                command(&["cc", "-DPIC", "-fPIC"], "/project/src/a.c"),
+                command(&["cc", "-DTEST"], "/project/third_party/b.c"),
+                command(&["cc"], "/project/src/generated/c.c"),
+            ]
+        );
+        filter.deduplicate(DeduplicationPolicy::Last);
+        assert_eq!(
+            filter.apply(commands())[0],
+            command(&["cc", "-D", "STATIC", "-DPIC"], "/project/src/a.c")
+        );
+        filter.deduplicate(DeduplicationPolicy::UnionOfDefines);
+        assert_eq!(
+            filter.apply(commands())[0],
+            command(&["cc", "-DPIC", "-fPIC", "-DSTATIC"], "/project/src/a.c")
+        );
+        assert_eq!(
+            filter.apply(vec![
+                command(&["cc", "-DFOO=1", "-DBAR"], "/project/src/a.c"),
+                command(
+                    &["cc", "-DFOO=2", "-D", "BAR=1", "-DBAZ=2"],
+                    "/project/src/a.c"
+                ),
+            ]),
+            vec![command(
+                &["cc", "-DFOO=1", "-DBAR", "-DBAZ=2"],
+                "/project/src/a.c"
+            )]
+        );
+    }
+}
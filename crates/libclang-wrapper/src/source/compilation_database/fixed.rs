@@ -0,0 +1,76 @@
+use crate::source::compilation_database::{
+    absolute, resolve_arguments, CompilationDatabase, CompilationDatabaseError, Parsed,
+};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// stands in for the compiler executable so commands look like the ones from
+// `compile_commands.json` and go through the same adjusters
+const COMPILER_PLACEHOLDER: &str = "clang-tool";
+
+/// Uses the same arguments for every file, like clang's `FixedCompilationDatabase`.
+/// The arguments do not name any files, so the ones to parse are given when creating it;
+/// `command_for` works for any file.
+pub struct FixedCompilationDatabase {
+    args: Vec<String>,
+    directory: String,
+    files: Vec<String>,
+}
+
+impl FixedCompilationDatabase {
+    /// Relative paths in `args` are resolved against `directory`, `files` are the ones
+    /// `commands` returns, in this order.
+    pub fn new(directory: &Path, args: Vec<String>, files: &[&Path]) -> FixedCompilationDatabase {
+        let directory = absolute(directory);
+        FixedCompilationDatabase {
+            args: resolve_arguments(Path::new(&directory), args),
+            directory,
+            files: files.iter().map(|file| absolute(file)).collect(),
+        }
+    }
+
+    /// Reads `compile_flags.txt`, one argument per line, relative to the file's directory.
+    pub fn from_flags_file(
+        file: &Path,
+        files: &[&Path],
+    ) -> Result<FixedCompilationDatabase, CompilationDatabaseError> {
+        let error = |error| CompilationDatabaseError::new(file, error);
+        let args = fs::read_to_string(file)
+            .map_err(error)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_owned())
+            .collect();
+        let file = env::current_dir().map_err(error)?.join(file);
+        let directory = file.parent().unwrap_or(&file);
+        Ok(FixedCompilationDatabase::new(directory, args, files))
+    }
+}
+
+impl CompilationDatabase for FixedCompilationDatabase {
+    fn commands(&self) -> Vec<Parsed> {
+        self.files
+            .iter()
+            .map(|file| self.command(file.clone()))
+            .collect()
+    }
+
+    fn command_for(&self, file: &Path) -> Option<Parsed> {
+        Some(self.command(absolute(file)))
+    }
+}
+
+impl FixedCompilationDatabase {
+    fn command(&self, file: String) -> Parsed {
+        let mut args = vec![COMPILER_PLACEHOLDER.to_owned()];
+        args.extend(self.args.iter().cloned());
+        args.push(file.clone());
+        Parsed {
+            args,
+            file,
+            directory: self.directory.clone(),
+        }
+    }
+}
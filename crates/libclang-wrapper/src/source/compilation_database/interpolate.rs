@@ -0,0 +1,174 @@
+use crate::source::compilation_database::{resolve_path, Parsed};
+use std::path::Path;
+
+const HEADER_EXTENSIONS: [&str; 8] = ["h", "H", "hh", "hp", "hpp", "hxx", "h++", "inl"];
+
+fn is_header(file: &Path) -> bool {
+    file.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| HEADER_EXTENSIONS.contains(&extension))
+}
+
+fn stem(file: &Path) -> &str {
+    file.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+}
+
+fn common_prefix<T: PartialEq>(
+    lhs: impl Iterator<Item = T>,
+    rhs: impl Iterator<Item = T>,
+) -> usize {
+    lhs.zip(rhs).take_while(|(lhs, rhs)| lhs == rhs).count()
+}
+
+// candidates are ranked by an exact stem match first, then by how many directories
+// they share with the file and finally by how much of the stem they share,
+// so `include/foo.h` borrows from `src/foo.cpp` before `include/bar.cpp`
+fn score(candidate: &Path, file: &Path) -> (bool, usize, usize) {
+    let same_stem = stem(candidate) == stem(file);
+    let shared_directories = match (candidate.parent(), file.parent()) {
+        (Some(lhs), Some(rhs)) => common_prefix(lhs.components(), rhs.components()),
+        _ => 0,
+    };
+    let shared_stem = common_prefix(stem(candidate).chars(), stem(file).chars());
+    (same_stem, shared_directories, shared_stem)
+}
+
+// the borrowed command compiled another file, so its language has to be
+// turned into the header flavour and references to the original file replaced
+fn transfer(command: &Parsed, file: &str) -> Parsed {
+    let target_is_header = is_header(Path::new(file));
+    let mut language = None;
+    let mut args = vec![];
+    let mut original = command.args.iter();
+    while let Some(arg) = original.next() {
+        if arg == "-x" {
+            language = original.next().cloned();
+        } else if let Some(value) = arg.strip_prefix("-x") {
+            language = Some(value.to_owned());
+        } else if !arg.starts_with('-')
+            && resolve_path(Path::new(&command.directory), arg) == command.file
+        {
+            args.push(file.to_owned());
+        } else {
+            args.push(arg.clone());
+        }
+    }
+    let language = if target_is_header {
+        let is_c = match &language {
+            Some(language) => language.starts_with('c') && !language.starts_with("c++"),
+            None => Path::new(&command.file)
+                .extension()
+                .is_some_and(|extension| extension == "c"),
+        };
+        Some(if is_c { "c-header" } else { "c++-header" }.to_owned())
+    } else {
+        language.map(|language| language.trim_end_matches("-header").to_owned())
+    };
+    if let Some(language) = language {
+        // keep the compiler executable first, adjusters expect it there
+        let position = args
+            .iter()
+            .take(1)
+            .filter(|arg| !arg.starts_with('-'))
+            .count();
+        args.insert(position, language);
+        args.insert(position, "-x".to_owned());
+    }
+    Parsed {
+        args,
+        file: file.to_owned(),
+        directory: command.directory.clone(),
+    }
+}
+
+/// Builds a command for `file`, which must be absolute and normalized, from the best
+/// matching entry of `commands`, similar to clangd's `InterpolatingCompilationDatabase`.
+pub fn infer(commands: &[Parsed], file: &str) -> Option<Parsed> {
+    let target = Path::new(file);
+    let mut best: Option<(&Parsed, (bool, usize, usize))> = None;
+    for command in commands {
+        let current = score(Path::new(&command.file), target);
+        // earlier entries win ties
+        if best.is_none_or(|(_, best)| current > best) {
+            best = Some((command, current));
+        }
+    }
+    best.map(|(command, _)| transfer(command, file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(args: &[&str], file: &str) -> Parsed {
+        Parsed {
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+            file: file.to_owned(),
+            directory: "/project/build".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_infer_prefers_same_stem() {
+        let commands = vec![
+            command(
+                &["clang++", "-DBAR", "-c", "../include/bar.cpp"],
+                "/project/include/bar.cpp",
+            ),
+            command(
+                &["clang++", "-DFOO", "-c", "../src/foo.cpp"],
+                "/project/src/foo.cpp",
+            ),
+        ];
+        assert_eq!(
+            infer(&commands, "/project/include/foo.h"),
+            Some(command(
+                &[
+                    "clang++",
+                    "-x",
+                    "c++-header",
+                    "-DFOO",
+                    "-c",
+                    "/project/include/foo.h"
+                ],
+                "/project/include/foo.h"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_infer_prefers_closer_directory() {
+        let commands = vec![
+            command(
+                &["cc", "-DOTHER", "/project/other/a.c"],
+                "/project/other/a.c",
+            ),
+            command(&["cc", "-DLIB", "/project/lib/b.c"], "/project/lib/b.c"),
+        ];
+        assert_eq!(
+            infer(&commands, "/project/lib/c.h"),
+            Some(command(
+                &["cc", "-x", "c-header", "-DLIB", "/project/lib/c.h"],
+                "/project/lib/c.h"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_infer_keeps_source_language() {
+        let commands = vec![command(
+            &["clang++", "-xc++-header", "/project/include/a.h"],
+            "/project/include/a.h",
+        )];
+        assert_eq!(
+            infer(&commands, "/project/src/a.cpp"),
+            Some(command(
+                &["clang++", "-x", "c++", "/project/src/a.cpp"],
+                "/project/src/a.cpp"
+            ))
+        );
+        assert_eq!(infer(&[], "/project/src/a.cpp"), None);
+    }
+}
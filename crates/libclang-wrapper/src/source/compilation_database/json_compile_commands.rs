@@ -1,5 +1,8 @@
-use crate::source::compilation_database::Parsed;
+use crate::source::compilation_database::{
+    command_line, CompilationDatabase, CompilationDatabaseError, Parsed,
+};
 use serde::Deserialize;
+use std::env;
 use std::error::Error;
 use std::fs::File;
 use std::io::BufReader;
@@ -7,23 +10,63 @@ use std::path::Path;
 
 #[derive(Deserialize, PartialEq, Debug)]
 pub struct Command {
-    pub command: String,
+    pub directory: String,
+    pub arguments: Option<Vec<String>>,
+    pub command: Option<String>,
     pub file: String,
 }
 
-pub fn from_file(file: &Path) -> Result<Vec<Parsed>, Box<dyn Error>> {
-    let file = File::open(file)?;
-    let reader = BufReader::new(file);
+impl Command {
+    // `arguments` is already split and so is preferred over `command` when both are present
+    fn into_args(self) -> Result<Vec<String>, Box<dyn Error>> {
+        match (self.arguments, self.command) {
+            (Some(arguments), _) => Ok(arguments),
+            (None, Some(command)) => command_line::split(&command),
+            (None, None) => Err(format!(
+                "compilation database entry for `{}` has neither `arguments` nor `command`",
+                self.file
+            )
+            .into()),
+        }
+    }
+}
+
+/// `compile_commands.json` as written by CMake, Bear or Ninja.
+pub struct JsonCompilationDatabase {
+    pub commands: Vec<Parsed>,
+}
+
+impl JsonCompilationDatabase {
+    pub fn new(file: &Path) -> Result<JsonCompilationDatabase, CompilationDatabaseError> {
+        Ok(JsonCompilationDatabase {
+            commands: from_file(file)
+                .map_err(|error| CompilationDatabaseError::new(file, error))?,
+        })
+    }
+}
+
+impl CompilationDatabase for JsonCompilationDatabase {
+    fn commands(&self) -> Vec<Parsed> {
+        self.commands.clone()
+    }
+
+    fn command_for(&self, file: &Path) -> Option<Parsed> {
+        self.commands.command_for(file)
+    }
+}
+
+fn from_file(file: &Path) -> Result<Vec<Parsed>, Box<dyn Error>> {
+    // relative `directory` entries are resolved against the database location
+    let base = env::current_dir()?.join(file);
+    let base = base.parent().unwrap_or(&base);
+    let reader = BufReader::new(File::open(file)?);
     let commands: Vec<Command> = serde_json::from_reader(reader)?;
-    Ok(commands
+    commands
         .into_iter()
-        .map(|val| Parsed {
-            args: val
-                .command
-                .split_whitespace()
-                .map(|val| val.to_owned())
-                .collect(),
-            file: val.file,
+        .map(|val| {
+            let directory = val.directory.clone();
+            let file = val.file.clone();
+            Ok(Parsed::resolve(base, &directory, &file, val.into_args()?))
         })
-        .collect())
+        .collect()
 }
@@ -1,22 +1,258 @@
+mod command_line;
+mod discovery;
+mod filter;
+mod fixed;
+mod interpolate;
 mod json_compile_commands;
 
+pub use discovery::{discover, AutoCompilationDatabase};
+pub use filter::{CommandFilter, DeduplicationPolicy, FilePattern};
+pub use fixed::FixedCompilationDatabase;
+pub use json_compile_commands::JsonCompilationDatabase;
+use std::env;
 use std::error::Error;
-use std::path::Path;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct Parsed {
     pub args: Vec<String>,
     pub file: String,
+    pub directory: String,
 }
 
-pub struct CompilationDatabase {
-    pub commands: Vec<Parsed>,
+/// A compilation database which could not be read, or could not be found for `path`.
+#[derive(Debug)]
+pub struct CompilationDatabaseError {
+    pub path: PathBuf,
+    pub message: String,
 }
 
-impl CompilationDatabase {
-    pub fn new(file: &Path) -> Result<CompilationDatabase, Box<dyn Error>> {
-        Ok(CompilationDatabase {
-            commands: json_compile_commands::from_file(file)?,
-        })
+impl CompilationDatabaseError {
+    pub(crate) fn new(path: &Path, message: impl ToString) -> CompilationDatabaseError {
+        CompilationDatabaseError {
+            path: path.to_path_buf(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for CompilationDatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.path.display(), self.message)
+    }
+}
+
+impl Error for CompilationDatabaseError {}
+
+/// Source of compile commands for `Source::from_compilation_database`.
+/// Commands are compiler invocations, `ArgumentsAdjuster::standard` turns them into
+/// arguments libclang accepts.
+pub trait CompilationDatabase {
+    /// Returns every command listed in the database.
+    fn commands(&self) -> Vec<Parsed>;
+
+    /// Returns the command for `file`, relative paths are taken from the current directory.
+    fn command_for(&self, file: &Path) -> Option<Parsed>;
+
+    /// Returns a command for each of `files` the database can provide one for, the result is
+    /// a database itself and can be handed to `Source::from_compilation_database`.
+    fn for_files(&self, files: &[&Path]) -> Vec<Parsed> {
+        files
+            .iter()
+            .filter_map(|file| self.command_for(file))
+            .collect()
+    }
+
+    /// Returns the commands `filter` keeps, the result is a database itself.
+    fn filtered(&self, filter: &CommandFilter) -> Vec<Parsed> {
+        filter.apply(self.commands())
+    }
+}
+
+/// Files which are not in the list, like headers, get flags borrowed from the entry
+/// closest to them.
+impl CompilationDatabase for Vec<Parsed> {
+    fn commands(&self) -> Vec<Parsed> {
+        self.clone()
+    }
+
+    fn command_for(&self, file: &Path) -> Option<Parsed> {
+        let file = absolute(file);
+        match self.iter().find(|command| command.file == file) {
+            Some(command) => Some(command.clone()),
+            None => interpolate::infer(self, &file),
+        }
+    }
+}
+
+// flags which take a path either as a separate argument or glued to the flag itself,
+// longer flags go first so `-include-pch` is not taken for `-include` + `-pch`
+const PATH_FLAGS: [&str; 6] = [
+    "-include-pch",
+    "-isystem",
+    "-include",
+    "-iquote",
+    "--sysroot",
+    "-I",
+];
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => match result.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    result.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => (),
+                _ => result.push(component),
+            },
+            _ => result.push(component),
+        }
+    }
+    result
+}
+
+pub(crate) fn absolute(path: &Path) -> String {
+    match env::current_dir() {
+        Ok(directory) => resolve_path(&directory, &path.to_string_lossy()),
+        Err(_) => normalize(path).to_string_lossy().into_owned(),
+    }
+}
+
+pub(crate) fn resolve_path(directory: &Path, path: &str) -> String {
+    normalize(&directory.join(path))
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn resolve_arguments(directory: &Path, args: Vec<String>) -> Vec<String> {
+    let mut result = Vec::with_capacity(args.len());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let flag = PATH_FLAGS
+            .iter()
+            .find(|flag| arg.starts_with(*flag))
+            .copied();
+        match flag {
+            Some(flag) if arg == flag => {
+                result.push(arg);
+                if let Some(value) = args.next() {
+                    result.push(resolve_path(directory, &value));
+                }
+            }
+            Some("--sysroot") => match arg.strip_prefix("--sysroot=") {
+                Some(value) => result.push(format!("--sysroot={}", resolve_path(directory, value))),
+                None => result.push(arg),
+            },
+            Some(flag) => {
+                let value = resolve_path(directory, &arg[flag.len()..]);
+                result.push(format!("{}{}", flag, value));
+            }
+            None => result.push(arg),
+        }
+    }
+    result
+}
+
+impl Parsed {
+    /// Resolves `file` and path-bearing flags against `directory`, which itself is resolved
+    /// against `base` if relative.
+    fn resolve(base: &Path, directory: &str, file: &str, args: Vec<String>) -> Parsed {
+        let directory = normalize(&base.join(directory));
+        Parsed {
+            args: resolve_arguments(&directory, args),
+            file: resolve_path(&directory, file),
+            directory: directory.to_string_lossy().into_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_owned(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("/build/./debug/../src/a.cpp")),
+            PathBuf::from("/build/src/a.cpp")
+        );
+        assert_eq!(normalize(Path::new("/../a.cpp")), PathBuf::from("/a.cpp"));
+        assert_eq!(normalize(Path::new("../a/./b")), PathBuf::from("../a/b"));
+    }
+
+    #[test]
+    fn test_resolve_arguments() {
+        let directory = Path::new("/build");
+        assert_eq!(
+            resolve_arguments(
+                directory,
+                to_owned(&[
+                    "clang++",
+                    "-Iinclude",
+                    "-I",
+                    "../third_party",
+                    "-I/usr/include",
+                    "-isystem",
+                    "sys",
+                    "-isystemsys2",
+                    "-include",
+                    "config.h",
+                    "-include-pch",
+                    "pch.h.pch",
+                    "-iquote",
+                    "quoted",
+                    "--sysroot=root",
+                    "--sysroot",
+                    "/abs/root",
+                    "-std=c++14",
+                ])
+            ),
+            to_owned(&[
+                "clang++",
+                "-I/build/include",
+                "-I",
+                "/third_party",
+                "-I/usr/include",
+                "-isystem",
+                "/build/sys",
+                "-isystem/build/sys2",
+                "-include",
+                "/build/config.h",
+                "-include-pch",
+                "/build/pch.h.pch",
+                "-iquote",
+                "/build/quoted",
+                "--sysroot=/build/root",
+                "--sysroot",
+                "/abs/root",
+                "-std=c++14",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_directory() {
+        let parsed = Parsed::resolve(
+            Path::new("/project/build"),
+            "..",
+            "./src/main.cpp",
+            to_owned(&["clang++", "-Iinclude"]),
+        );
+        assert_eq!(
+            parsed,
+            Parsed {
+                args: to_owned(&["clang++", "-I/project/include"]),
+                file: "/project/src/main.cpp".to_owned(),
+                directory: "/project".to_owned(),
+            }
+        );
     }
 }
@@ -1,15 +1,96 @@
-pub struct CompilerInstance {}
+use crate::source::ParsingError;
+use clang_sys::{SharedLibrary, Version};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-impl CompilerInstance {
-    pub fn new() -> CompilerInstance {
-        clang_sys::load().unwrap();
-        clang_sys::get_library().unwrap();
-        CompilerInstance {}
+static SHARED: Mutex<Option<Arc<SharedLibrary>>> = Mutex::new(None);
+
+/// Reference counted handle to a loaded libclang.
+/// clang-sys resolves libclang functions through a thread local library, so every `Index`
+/// and translation unit keeps a handle and activates it on the thread using it, which keeps
+/// the library loaded for as long as any of them is alive on any thread.
+#[derive(Clone, Debug)]
+pub struct CompilerInstance {
+    library: Arc<SharedLibrary>,
+}
+
+fn load() -> Result<Arc<SharedLibrary>, ParsingError> {
+    clang_sys::load_manually()
+        .map(Arc::new)
+        .map_err(ParsingError::LibraryLoadFailure)
+}
+
+// `path` itself unless it is a directory, `libclang.so` in it is preferred over versioned names
+fn library_file(path: &Path) -> Result<PathBuf, ParsingError> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
     }
+    let unversioned = format!("{}clang{}", DLL_PREFIX, DLL_SUFFIX);
+    let prefix = format!("{}clang", DLL_PREFIX);
+    let mut candidates: Vec<String> = fs::read_dir(path)
+        .map_err(|error| {
+            ParsingError::LibraryLoadFailure(format!("{}: {}", path.display(), error))
+        })?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with(&prefix) && name.contains(DLL_SUFFIX))
+        .collect();
+    candidates.sort();
+    let file = match candidates.iter().find(|name| **name == unversioned) {
+        Some(name) => name,
+        None => candidates.last().ok_or_else(|| {
+            ParsingError::LibraryLoadFailure(format!("no libclang in {}", path.display()))
+        })?,
+    };
+    Ok(path.join(file))
 }
 
-impl Drop for CompilerInstance {
-    fn drop(&mut self) {
-        clang_sys::unload().unwrap();
+impl CompilerInstance {
+    /// Process wide instance, libclang is found the way clang-sys does it
+    /// (`LIBCLANG_PATH`, `llvm-config`, system directories) and loaded once.
+    pub fn shared() -> Result<CompilerInstance, ParsingError> {
+        let mut shared = SHARED.lock().unwrap();
+        let library = match &*shared {
+            Some(library) => library.clone(),
+            None => {
+                let library = load()?;
+                *shared = Some(library.clone());
+                library
+            }
+        };
+        Ok(CompilerInstance::from_library(library))
+    }
+
+    /// Loads a specific libclang, `path` is either the shared library itself or a directory
+    /// containing it. Each call loads a separate instance.
+    pub fn from_path(path: &Path) -> Result<CompilerInstance, ParsingError> {
+        let library =
+            clang_sys::load_from(&library_file(path)?).map_err(ParsingError::LibraryLoadFailure)?;
+        Ok(CompilerInstance::from_library(Arc::new(library)))
+    }
+
+    fn from_library(library: Arc<SharedLibrary>) -> CompilerInstance {
+        let result = CompilerInstance { library };
+        result.activate();
+        result
+    }
+
+    /// Makes this library the one libclang calls on the current thread go to.
+    pub(crate) fn activate(&self) {
+        let is_active =
+            clang_sys::get_library().is_some_and(|library| Arc::ptr_eq(&library, &self.library));
+        if !is_active {
+            clang_sys::set_library(Some(self.library.clone()));
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.library.path()
+    }
+
+    /// Minimum libclang version the library provides, `None` if it is older than 3.5.
+    pub fn version(&self) -> Option<Version> {
+        self.library.version()
     }
 }
@@ -0,0 +1,276 @@
+use crate::source::translation_unit::index::{DeclarationFromPHCMode, DiagnosticsMode, Index};
+use crate::source::translation_unit::{
+    Diagnostic, Entry, ExtractionOptions, TUError, TUOptionsBuilder, TranslationUnit,
+    TraversalFilter, UnsavedFile, TU,
+};
+use crate::source::{CompilerInstance, ParseConfig, ParsingError};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, Read, Write};
+#[cfg(unix)]
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Where translation units are parsed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Isolation {
+    /// In the calling process, with a timeout the parse continues in the background after
+    /// it is reported as timed out, as libclang can not be interrupted.
+    #[default]
+    InProcess,
+    /// In a new process per translation unit running the given executable, which has to call
+    /// `serve_parse_request`, e.g. the `parse_worker` binary of this crate. Crashes only fail
+    /// the translation unit and timed out workers are killed.
+    /// The predicate of a `TraversalFilter` can not be sent to a worker.
+    Subprocess(PathBuf),
+}
+
+#[derive(Serialize, Deserialize)]
+struct WorkerRequest {
+    file_name: String,
+    phc_mode: DeclarationFromPHCMode,
+    diagnostics_mode: DiagnosticsMode,
+    arguments: Vec<String>,
+    options: TUOptionsBuilder,
+    unsaved_files: Vec<UnsavedFile>,
+    compiler: Option<PathBuf>,
+    traversal_filter: TraversalFilter,
+    extraction_options: ExtractionOptions,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WorkerResponse {
+    ast: Entry,
+    diagnostics: Vec<Diagnostic>,
+    parse_duration: Duration,
+}
+
+fn parse_in_process(
+    phc_mode: DeclarationFromPHCMode,
+    diagnostics_mode: DiagnosticsMode,
+    file_name: String,
+    args: Vec<String>,
+    options: &TUOptionsBuilder,
+    config: &ParseConfig,
+) -> Result<TU, TUError> {
+    let index = config
+        .compiler()
+        .and_then(|ci| Index::new(phc_mode, diagnostics_mode, ci));
+    match index {
+        Ok(index) => TU::new(file_name, index, args, options, config),
+        Err(error) => Err(TUError::new(error, file_name, args)),
+    }
+}
+
+fn parse_in_thread(
+    phc_mode: DeclarationFromPHCMode,
+    diagnostics_mode: DiagnosticsMode,
+    file_name: String,
+    args: Vec<String>,
+    options: &TUOptionsBuilder,
+    config: &ParseConfig,
+    timeout: Duration,
+) -> Result<TU, TUError> {
+    let (tx, rx) = channel();
+    {
+        let file_name = file_name.clone();
+        let args = args.clone();
+        let options = *options;
+        let config = config.clone();
+        thread::spawn(move || {
+            let result = parse_in_process(
+                phc_mode,
+                diagnostics_mode,
+                file_name,
+                args,
+                &options,
+                &config,
+            );
+            // fails only when the parse timed out
+            let _ = tx.send(result);
+        });
+    }
+    match rx.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(TUError::new(
+            ParsingError::Timeout(file_name.clone()),
+            file_name,
+            args,
+        )),
+        Err(RecvTimeoutError::Disconnected) => Err(TUError::new(
+            ParsingError::Crash(file_name.clone()),
+            file_name,
+            args,
+        )),
+    }
+}
+
+fn parse_in_subprocess(
+    worker: &Path,
+    request: &WorkerRequest,
+    timeout: Option<Duration>,
+) -> Result<WorkerResponse, ParsingError> {
+    let file_name = &request.file_name;
+    let worker_failure = |error: &dyn Display| {
+        ParsingError::WorkerFailure(format!(
+            "{} (worker `{}`): {}",
+            file_name,
+            worker.display(),
+            error
+        ))
+    };
+    let mut child = Command::new(worker)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|error| worker_failure(&error))?;
+    let request = serde_json::to_vec(request).map_err(|error| worker_failure(&error))?;
+    let mut stdin = child.stdin.take().unwrap();
+    // a worker crashing before reading its request shows in the exit status
+    let _ = stdin.write_all(&request);
+    drop(stdin);
+    // read while waiting, a worker blocked on a full pipe would never exit
+    let mut stdout = child.stdout.take().unwrap();
+    let output = thread::spawn(move || {
+        let mut output = vec![];
+        stdout.read_to_end(&mut output).map(|_| output)
+    });
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait().map_err(|error| worker_failure(&error))? {
+            Some(status) => break status,
+            None if timeout.is_some_and(|timeout| start.elapsed() >= timeout) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(ParsingError::Timeout(file_name.clone()));
+            }
+            None => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    if is_crash(status) {
+        return Err(ParsingError::Crash(file_name.clone()));
+    }
+    if !status.success() {
+        return Err(worker_failure(&status));
+    }
+    let output = output
+        .join()
+        .unwrap()
+        .map_err(|error| worker_failure(&error))?;
+    let response: Result<WorkerResponse, ParsingError> =
+        serde_json::from_slice(&output).map_err(|error| worker_failure(&error))?;
+    response
+}
+
+// a worker failing on its own, e.g. on a request it can not read, exits with an error code
+#[cfg(unix)]
+fn is_crash(status: ExitStatus) -> bool {
+    status.signal().is_some()
+}
+
+#[cfg(not(unix))]
+fn is_crash(status: ExitStatus) -> bool {
+    !status.success()
+}
+
+/// Parses a translation unit the way `config` asks for.
+pub(crate) fn parse(
+    phc_mode: DeclarationFromPHCMode,
+    diagnostics_mode: DiagnosticsMode,
+    file_name: String,
+    args: Vec<String>,
+    options: &TUOptionsBuilder,
+    config: &ParseConfig,
+) -> Result<TU, TUError> {
+    match (config.isolation_mode(), config.parse_timeout()) {
+        (Isolation::InProcess, None) => {
+            parse_in_process(phc_mode, diagnostics_mode, file_name, args, options, config)
+        }
+        (Isolation::InProcess, Some(timeout)) => parse_in_thread(
+            phc_mode,
+            diagnostics_mode,
+            file_name,
+            args,
+            options,
+            config,
+            timeout,
+        ),
+        (Isolation::Subprocess(worker), timeout) => {
+            if config.filter().has_predicate() {
+                let error = ParsingError::WorkerFailure(format!(
+                    "{}: traversal filter predicates can not be sent to a worker",
+                    file_name
+                ));
+                return Err(TUError::new(error, file_name, args));
+            }
+            let request = WorkerRequest {
+                file_name,
+                phc_mode,
+                diagnostics_mode,
+                arguments: args,
+                options: *options,
+                unsaved_files: config.unsaved().to_vec(),
+                compiler: config.compiler_path().map(Path::to_path_buf),
+                traversal_filter: config.filter().clone(),
+                extraction_options: config.extraction().clone(),
+            };
+            let result = parse_in_subprocess(worker, &request, timeout);
+            let WorkerRequest {
+                file_name,
+                arguments,
+                ..
+            } = request;
+            match result {
+                Ok(response) => Ok(TU::from_parts(
+                    response.ast,
+                    response.diagnostics,
+                    file_name,
+                    arguments,
+                    response.parse_duration,
+                )),
+                Err(error) => Err(TUError::new(error, file_name, arguments)),
+            }
+        }
+    }
+}
+
+fn serve(request: WorkerRequest) -> Result<WorkerResponse, ParsingError> {
+    let mut config = ParseConfig::new();
+    config
+        .unsaved_files(request.unsaved_files)
+        .traversal_filter(request.traversal_filter)
+        .extraction_options(request.extraction_options);
+    if let Some(compiler) = request.compiler {
+        config.compiler_instance(CompilerInstance::from_path(&compiler)?);
+    }
+    let translation_unit = TranslationUnit::parse(
+        request.file_name,
+        request.phc_mode,
+        request.diagnostics_mode,
+        request.arguments,
+        request.options,
+        &config,
+    )
+    // the requesting process has the file name and arguments already
+    .map_err(|error| error.error)?;
+    let (ast, diagnostics, info) = translation_unit.into_tu().into_parts();
+    Ok(WorkerResponse {
+        ast,
+        diagnostics,
+        parse_duration: info.parse_duration,
+    })
+}
+
+/// Entry point of isolated workers, see `Isolation::Subprocess`. Reads one request from
+/// stdin and writes the result to stdout.
+pub fn serve_parse_request() -> Result<(), Box<dyn Error>> {
+    let request: WorkerRequest = serde_json::from_reader(io::stdin().lock())?;
+    let response = serve(request);
+    serde_json::to_writer(io::stdout().lock(), &response)?;
+    Ok(())
+}
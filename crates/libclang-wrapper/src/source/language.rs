@@ -0,0 +1,29 @@
+/// Language of source code without a file name to deduce it from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    C,
+    Cpp,
+    ObjectiveC,
+    ObjectiveCpp,
+}
+
+impl Language {
+    /// Value of clang's `-x` option.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::C => "c",
+            Language::Cpp => "c++",
+            Language::ObjectiveC => "objective-c",
+            Language::ObjectiveCpp => "objective-c++",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::ObjectiveC => "m",
+            Language::ObjectiveCpp => "mm",
+        }
+    }
+}
@@ -1,21 +1,43 @@
+mod arguments_adjuster;
 mod compilation_database;
 mod compiler_instance;
+mod isolation;
+mod language;
+mod parse_config;
 mod translation_unit;
+mod tu_stream;
 
-pub use compilation_database::{CompilationDatabase, Parsed};
-use compiler_instance::CompilerInstance;
-use std::sync::mpsc::channel;
-use threadpool::ThreadPool;
-use translation_unit::index::Index;
+pub use arguments_adjuster::{ArgumentInsertPosition, ArgumentsAdjuster};
+pub use compilation_database::{
+    discover, AutoCompilationDatabase, CommandFilter, CompilationDatabase,
+    CompilationDatabaseError, DeduplicationPolicy, FilePattern, FixedCompilationDatabase,
+    JsonCompilationDatabase, Parsed,
+};
+pub use compiler_instance::CompilerInstance;
+pub use isolation::{serve_parse_request, Isolation};
+pub use language::Language;
+pub use parse_config::{CancellationToken, ParseConfig, Progress};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
 pub use translation_unit::index::{DeclarationFromPHCMode, DiagnosticsMode};
 pub use translation_unit::TUOptionsBuilder;
-use translation_unit::TU;
 pub use translation_unit::{
-    AccessSpecifierType, CodeSpan, ConstructorType, CursorKind, CursorType, Entry, Position,
-    TemplateArgumentKind, Virtuality,
+    AccessSpecifierType, BinaryOperatorKind, CodeSpan, ConstructorType, CursorKind, CursorType,
+    Entry, Position, TemplateArgumentKind, UnaryOperatorKind, Virtuality,
+};
+pub use translation_unit::{CharacterValue, Encoding, FloatValue, IntegerValue, StringValue};
+pub use translation_unit::{
+    Cursor, DeclarationChange, Diagnostic, ExtractionOptions, FixIt, KindCategory, Reparsed,
+    Severity, TUError, TUInfo, TranslationUnit, TraversalFilter, UnsavedFile, TU,
 };
+pub use translation_unit::{
+    ExceptionSpecification, FunctionAttributes, Linkage, RefQualifier, StorageClass,
+    ThreadLocalKind, VariableAttributes,
+};
+pub use tu_stream::TUStream;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ParsingError {
     FileNameConversionProblem(String),
     IndexCreationFailure,
@@ -23,60 +45,176 @@ pub enum ParsingError {
     Crash(String),
     InvalidArguments(String),
     ASTReadError(String),
-    UnknownError(i32),
+    ASTWriteError(String),
+    Cancelled(String),
+    Timeout(String),
+    /// an isolated worker could not be run, exited with an error or answered with something
+    /// unexpected
+    WorkerFailure(String),
+    LibraryLoadFailure(String),
+    /// a compilation database could not be read or discovered
+    CompilationDatabaseError(String),
+    /// the translation unit was parsed, but with errors, see `TU::without_errors`
+    CompilationErrors(String),
+    /// file name and the unexpected `CXErrorCode`
+    UnknownError(String, i32),
+}
+
+impl fmt::Display for ParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsingError::FileNameConversionProblem(file_name) => write!(
+                f,
+                "`{}` can not be passed to libclang, it contains a nul byte",
+                file_name
+            ),
+            ParsingError::IndexCreationFailure => write!(f, "libclang could not create an index"),
+            ParsingError::GenericFailure(file_name) => {
+                write!(f, "libclang failed to parse `{}`", file_name)
+            }
+            ParsingError::Crash(file_name) => write!(f, "libclang crashed parsing `{}`", file_name),
+            ParsingError::InvalidArguments(file_name) => {
+                write!(f, "invalid arguments for parsing `{}`", file_name)
+            }
+            ParsingError::ASTReadError(file_name) => {
+                write!(f, "could not read the AST file `{}`", file_name)
+            }
+            ParsingError::ASTWriteError(file_name) => {
+                write!(f, "could not write the AST file `{}`", file_name)
+            }
+            ParsingError::Cancelled(file_name) => {
+                write!(f, "parsing `{}` was cancelled", file_name)
+            }
+            ParsingError::Timeout(file_name) => write!(f, "parsing `{}` timed out", file_name),
+            ParsingError::WorkerFailure(message) => write!(f, "parse worker failed: {}", message),
+            ParsingError::LibraryLoadFailure(message) => {
+                write!(f, "could not load libclang: {}", message)
+            }
+            ParsingError::CompilationDatabaseError(message) => {
+                write!(f, "could not read the compilation database: {}", message)
+            }
+            ParsingError::CompilationErrors(file_name) => {
+                write!(f, "`{}` does not compile", file_name)
+            }
+            ParsingError::UnknownError(file_name, code) => write!(
+                f,
+                "libclang failed to parse `{}` with unknown error code {}",
+                file_name, code
+            ),
+        }
+    }
+}
+
+impl Error for ParsingError {}
+
+impl From<CompilationDatabaseError> for ParsingError {
+    fn from(error: CompilationDatabaseError) -> Self {
+        ParsingError::CompilationDatabaseError(error.to_string())
+    }
 }
 
 pub struct Source {
-    pub translation_units: Vec<Result<TU, ParsingError>>,
+    /// in the order of the files given, one per file
+    pub translation_units: Vec<Result<TU, TUError>>,
 }
 
 impl Source {
+    /// `.ast` files written by `TranslationUnit::save` are loaded instead of parsed, the same
+    /// goes for compilation database entries, so both can be mixed.
     pub fn from_file(
         file_name: String,
         phc_mode: DeclarationFromPHCMode,
         diagnostics_mode: DiagnosticsMode,
         command_line_args: Vec<String>,
         options: TUOptionsBuilder,
+        config: &ParseConfig,
     ) -> Result<Source, ParsingError> {
-        let ci = CompilerInstance::new();
-        let index = Index::new(phc_mode, diagnostics_mode, ci)?;
         let mut result = Source {
             translation_units: vec![],
         };
-        result
-            .translation_units
-            .push(TU::new(file_name, &index, command_line_args, &options));
+        let translation_unit = isolation::parse(
+            phc_mode,
+            diagnostics_mode,
+            file_name,
+            command_line_args,
+            &options,
+            config,
+        );
+        result.translation_units.push(translation_unit);
         Ok(result)
     }
 
+    /// Parses `code` as a file named `snippet.<extension>` that only exists in memory,
+    /// without printing diagnostics. `standard` is the value of `-std`, e.g. `c++17`.
+    pub fn from_string(
+        code: &str,
+        language: Language,
+        standard: Option<&str>,
+        extra_args: Vec<String>,
+    ) -> Result<Source, ParsingError> {
+        let file_name = format!("snippet.{}", language.extension());
+        let mut command_line_args = vec!["-x".to_owned(), language.name().to_owned()];
+        if let Some(standard) = standard {
+            command_line_args.push(format!("-std={}", standard));
+        }
+        command_line_args.extend(extra_args);
+        let mut config = ParseConfig::new();
+        config.unsaved_files(vec![UnsavedFile::new(&file_name, code)]);
+        Source::from_file(
+            file_name,
+            DeclarationFromPHCMode::Exclude,
+            DiagnosticsMode::Disabled,
+            command_line_args,
+            TUOptionsBuilder::new(),
+            &config,
+        )
+    }
+
     pub fn from_compilation_database(
         phc_mode: DeclarationFromPHCMode,
         diagnostics_mode: DiagnosticsMode,
-        compilation_database: CompilationDatabase,
+        compilation_database: &dyn CompilationDatabase,
         options: TUOptionsBuilder,
+        adjuster: &ArgumentsAdjuster,
+        config: &ParseConfig,
     ) -> Result<Source, ParsingError> {
-        let n_workers = 16; // TODO make it configurable
-        let n_tasks = compilation_database.commands.len();
-        let thread_pool = ThreadPool::new(n_workers);
-        let (tx, rx) = channel();
-        for command in compilation_database.commands {
-            let tx = tx.clone();
-            thread_pool.execute(move || {
-                let ci = CompilerInstance::new();
-                let index = Index::new(phc_mode, diagnostics_mode, ci);
-                let err_msg = "Failed to create syncronization channel for thread pool";
-                match index {
-                    Ok(index) => tx
-                        .send(TU::new(command.file, &index, command.args, &options))
-                        .expect(err_msg),
-                    Err(err) => tx.send(Err(err)).expect(err_msg),
-                };
-                //          clang_sys::unload().unwrap();
-            });
+        let stream = Source::stream_compilation_database(
+            phc_mode,
+            diagnostics_mode,
+            compilation_database,
+            options,
+            adjuster,
+            config,
+        );
+        let mut results: Vec<_> = (0..stream.total()).map(|_| None).collect();
+        for (position, result) in stream {
+            results[position] = Some(result);
         }
-        let result = Source {
-            translation_units: rx.iter().take(n_tasks).collect(),
-        };
+        let translation_units = results
+            .into_iter()
+            .map(|result| result.expect("the stream yields every position once"))
+            .collect();
+        let result = Source { translation_units };
         Ok(result)
     }
+
+    /// Parses the compilation database in the background, yielding each translation unit
+    /// as soon as it is parsed so it can be processed and dropped before the rest is done.
+    pub fn stream_compilation_database(
+        phc_mode: DeclarationFromPHCMode,
+        diagnostics_mode: DiagnosticsMode,
+        compilation_database: &dyn CompilationDatabase,
+        options: TUOptionsBuilder,
+        adjuster: &ArgumentsAdjuster,
+        config: &ParseConfig,
+    ) -> TUStream {
+        TUStream::new(
+            phc_mode,
+            diagnostics_mode,
+            compilation_database.commands(),
+            options,
+            adjuster,
+            config,
+        )
+    }
 }
@@ -0,0 +1,186 @@
+use crate::source::{
+    CompilerInstance, ExtractionOptions, Isolation, ParsingError, TraversalFilter, UnsavedFile,
+};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Reported after each translation unit of a batch finished parsing.
+#[derive(Debug)]
+pub struct Progress<'a> {
+    pub file_name: &'a str,
+    /// position of the file in the compilation database
+    pub index: usize,
+    /// number of translation units finished so far, including this one
+    pub completed: usize,
+    pub total: usize,
+    /// time since the batch started
+    pub elapsed: Duration,
+}
+
+type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+/// Shared flag to stop a batch, e.g. from a Ctrl-C handler. Translation units already being
+/// parsed are finished, the remaining ones fail with `ParsingError::Cancelled`.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Clone)]
+pub struct ParseConfig {
+    workers: usize,
+    progress: Option<ProgressCallback>,
+    cancellation_token: CancellationToken,
+    compiler_instance: Option<CompilerInstance>,
+    unsaved_files: Arc<Vec<UnsavedFile>>,
+    traversal_filter: TraversalFilter,
+    extraction_options: ExtractionOptions,
+    timeout: Option<Duration>,
+    isolation: Isolation,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseConfig {
+    /// Uses one worker per available CPU and the process wide libclang, without progress
+    /// reporting.
+    pub fn new() -> ParseConfig {
+        ParseConfig {
+            workers: thread::available_parallelism()
+                .map(|workers| workers.get())
+                .unwrap_or(1),
+            progress: None,
+            cancellation_token: CancellationToken::new(),
+            compiler_instance: None,
+            unsaved_files: Arc::new(vec![]),
+            traversal_filter: TraversalFilter::new(),
+            extraction_options: ExtractionOptions::new(),
+            timeout: None,
+            isolation: Isolation::InProcess,
+        }
+    }
+
+    pub fn workers(&mut self, workers: usize) -> &mut ParseConfig {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// `callback` is called on the thread consuming the results, in completion order.
+    pub fn progress<F>(&mut self, callback: F) -> &mut ParseConfig
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn cancellation_token(&mut self, token: CancellationToken) -> &mut ParseConfig {
+        self.cancellation_token = token;
+        self
+    }
+
+    /// Parses with a specific libclang instead of `CompilerInstance::shared`.
+    pub fn compiler_instance(&mut self, compiler_instance: CompilerInstance) -> &mut ParseConfig {
+        self.compiler_instance = Some(compiler_instance);
+        self
+    }
+
+    /// In-memory contents used instead of the files on disk, for every translation unit.
+    pub fn unsaved_files(&mut self, unsaved_files: Vec<UnsavedFile>) -> &mut ParseConfig {
+        self.unsaved_files = Arc::new(unsaved_files);
+        self
+    }
+
+    /// Which files the extracted AST covers.
+    pub fn traversal_filter(&mut self, filter: TraversalFilter) -> &mut ParseConfig {
+        self.traversal_filter = filter;
+        self
+    }
+
+    /// Which cursors of the covered files the extracted AST contains.
+    pub fn extraction_options(&mut self, options: ExtractionOptions) -> &mut ParseConfig {
+        self.extraction_options = options;
+        self
+    }
+
+    /// Translation units taking longer fail with `ParsingError::Timeout`. Unless parsed with
+    /// `Isolation::Subprocess` this only reports the timeout: libclang can not be interrupted,
+    /// so the parse goes on in a detached thread, outside of `workers`, until it finishes.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut ParseConfig {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn isolation(&mut self, isolation: Isolation) -> &mut ParseConfig {
+        self.isolation = isolation;
+        self
+    }
+
+    pub(crate) fn compiler(&self) -> Result<CompilerInstance, ParsingError> {
+        match &self.compiler_instance {
+            Some(compiler_instance) => Ok(compiler_instance.clone()),
+            None => CompilerInstance::shared(),
+        }
+    }
+
+    pub(crate) fn unsaved(&self) -> &[UnsavedFile] {
+        &self.unsaved_files
+    }
+
+    pub(crate) fn filter(&self) -> &TraversalFilter {
+        &self.traversal_filter
+    }
+
+    pub(crate) fn extraction(&self) -> &ExtractionOptions {
+        &self.extraction_options
+    }
+
+    pub(crate) fn compiler_path(&self) -> Option<&Path> {
+        self.compiler_instance.as_ref().map(CompilerInstance::path)
+    }
+
+    pub(crate) fn parse_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub(crate) fn isolation_mode(&self) -> &Isolation {
+        &self.isolation
+    }
+
+    pub(crate) fn n_workers(&self) -> usize {
+        self.workers
+    }
+
+    pub(crate) fn report(&self, progress: &Progress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation_token.is_cancelled()
+    }
+}
@@ -1,8 +1,16 @@
+use crate::source::translation_unit::declaration::{
+    get_function_attributes, get_variable_attributes, FunctionAttributes, VariableAttributes,
+};
+use crate::source::translation_unit::literal::{
+    is_quoted, parse_character, parse_float, parse_integer, parse_string, CharacterValue, Encoding,
+    FloatValue, IntegerValue, StringValue,
+};
 use clang_sys::*;
+use serde::{Deserialize, Serialize};
 use std::ffi::CStr;
 use std::ptr;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum AccessSpecifierType {
     Invalid,
     Public,
@@ -10,7 +18,7 @@ pub enum AccessSpecifierType {
     Private,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum CursorType {
     Unexposed,
     Void,
@@ -71,7 +79,7 @@ pub enum CursorType {
     NotSupported(i32),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum TemplateArgumentKind {
     Null,
     Type,
@@ -85,7 +93,7 @@ pub enum TemplateArgumentKind {
     Invalid,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum ConstructorType {
     None,
     Converting,
@@ -94,19 +102,19 @@ pub enum ConstructorType {
     Move,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Position {
     pub file_name: String,
     pub line: u32,
     pub col: u32,
 }
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct CodeSpan {
     pub start_pos: Position,
     pub end_pos: Position,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Virtuality {
     NonVirtual,
     PureVirtual,
@@ -114,7 +122,173 @@ pub enum Virtuality {
     Static,
 }
 
-#[derive(Debug, PartialEq)]
+/// Operator of a `UnaryOperator`, read from its tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnaryOperatorKind {
+    PostIncrement,
+    PostDecrement,
+    PreIncrement,
+    PreDecrement,
+    AddressOf,
+    Dereference,
+    Plus,
+    Minus,
+    BitwiseNot,
+    LogicalNot,
+    Real,      // `__real`
+    Imaginary, // `__imag`
+    Extension, // `__extension__`
+    Coawait,
+    /// the token found instead of an operator, e.g. when the operator comes from a macro
+    NotSupported(String),
+}
+
+impl UnaryOperatorKind {
+    pub(crate) fn from_token(token: &str, postfix: bool) -> UnaryOperatorKind {
+        match (token, postfix) {
+            ("++", true) => UnaryOperatorKind::PostIncrement,
+            ("--", true) => UnaryOperatorKind::PostDecrement,
+            ("++", false) => UnaryOperatorKind::PreIncrement,
+            ("--", false) => UnaryOperatorKind::PreDecrement,
+            ("&", false) | ("bitand", false) => UnaryOperatorKind::AddressOf,
+            ("*", false) => UnaryOperatorKind::Dereference,
+            ("+", false) => UnaryOperatorKind::Plus,
+            ("-", false) => UnaryOperatorKind::Minus,
+            ("~", false) | ("compl", false) => UnaryOperatorKind::BitwiseNot,
+            ("!", false) | ("not", false) => UnaryOperatorKind::LogicalNot,
+            ("__real", false) | ("__real__", false) => UnaryOperatorKind::Real,
+            ("__imag", false) | ("__imag__", false) => UnaryOperatorKind::Imaginary,
+            ("__extension__", false) => UnaryOperatorKind::Extension,
+            ("co_await", false) => UnaryOperatorKind::Coawait,
+            _ => UnaryOperatorKind::NotSupported(token.to_owned()),
+        }
+    }
+
+    pub fn is_increment_or_decrement(&self) -> bool {
+        matches!(
+            self,
+            UnaryOperatorKind::PostIncrement
+                | UnaryOperatorKind::PostDecrement
+                | UnaryOperatorKind::PreIncrement
+                | UnaryOperatorKind::PreDecrement
+        )
+    }
+}
+
+/// Operator of a `BinaryOperator` or `CompoundAssignOperator`, read from its tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BinaryOperatorKind {
+    PointerToMemberDot,   // `.*`
+    PointerToMemberArrow, // `->*`
+    Multiply,
+    Divide,
+    Remainder,
+    Add,
+    Subtract,
+    ShiftLeft,
+    ShiftRight,
+    ThreeWayComparison, // `<=>`
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    BitwiseAnd,
+    BitwiseXor,
+    BitwiseOr,
+    LogicalAnd,
+    LogicalOr,
+    Assign,
+    MultiplyAssign,
+    DivideAssign,
+    RemainderAssign,
+    AddAssign,
+    SubtractAssign,
+    ShiftLeftAssign,
+    ShiftRightAssign,
+    BitwiseAndAssign,
+    BitwiseXorAssign,
+    BitwiseOrAssign,
+    Comma,
+    /// the token found instead of an operator, e.g. when the operator comes from a macro
+    NotSupported(String),
+}
+
+impl BinaryOperatorKind {
+    pub(crate) fn from_token(token: &str) -> BinaryOperatorKind {
+        match token {
+            ".*" => BinaryOperatorKind::PointerToMemberDot,
+            "->*" => BinaryOperatorKind::PointerToMemberArrow,
+            "*" => BinaryOperatorKind::Multiply,
+            "/" => BinaryOperatorKind::Divide,
+            "%" => BinaryOperatorKind::Remainder,
+            "+" => BinaryOperatorKind::Add,
+            "-" => BinaryOperatorKind::Subtract,
+            "<<" => BinaryOperatorKind::ShiftLeft,
+            ">>" => BinaryOperatorKind::ShiftRight,
+            "<=>" => BinaryOperatorKind::ThreeWayComparison,
+            "<" => BinaryOperatorKind::Less,
+            ">" => BinaryOperatorKind::Greater,
+            "<=" => BinaryOperatorKind::LessEqual,
+            ">=" => BinaryOperatorKind::GreaterEqual,
+            "==" => BinaryOperatorKind::Equal,
+            "!=" | "not_eq" => BinaryOperatorKind::NotEqual,
+            "&" | "bitand" => BinaryOperatorKind::BitwiseAnd,
+            "^" | "xor" => BinaryOperatorKind::BitwiseXor,
+            "|" | "bitor" => BinaryOperatorKind::BitwiseOr,
+            "&&" | "and" => BinaryOperatorKind::LogicalAnd,
+            "||" | "or" => BinaryOperatorKind::LogicalOr,
+            "=" => BinaryOperatorKind::Assign,
+            "*=" => BinaryOperatorKind::MultiplyAssign,
+            "/=" => BinaryOperatorKind::DivideAssign,
+            "%=" => BinaryOperatorKind::RemainderAssign,
+            "+=" => BinaryOperatorKind::AddAssign,
+            "-=" => BinaryOperatorKind::SubtractAssign,
+            "<<=" => BinaryOperatorKind::ShiftLeftAssign,
+            ">>=" => BinaryOperatorKind::ShiftRightAssign,
+            "&=" | "and_eq" => BinaryOperatorKind::BitwiseAndAssign,
+            "^=" | "xor_eq" => BinaryOperatorKind::BitwiseXorAssign,
+            "|=" | "or_eq" => BinaryOperatorKind::BitwiseOrAssign,
+            "," => BinaryOperatorKind::Comma,
+            _ => BinaryOperatorKind::NotSupported(token.to_owned()),
+        }
+    }
+
+    /// `=` and the compound assignments.
+    pub fn is_assignment(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperatorKind::Assign
+                | BinaryOperatorKind::MultiplyAssign
+                | BinaryOperatorKind::DivideAssign
+                | BinaryOperatorKind::RemainderAssign
+                | BinaryOperatorKind::AddAssign
+                | BinaryOperatorKind::SubtractAssign
+                | BinaryOperatorKind::ShiftLeftAssign
+                | BinaryOperatorKind::ShiftRightAssign
+                | BinaryOperatorKind::BitwiseAndAssign
+                | BinaryOperatorKind::BitwiseXorAssign
+                | BinaryOperatorKind::BitwiseOrAssign
+        )
+    }
+
+    /// Relational and equality operators, including `<=>`.
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperatorKind::ThreeWayComparison
+                | BinaryOperatorKind::Less
+                | BinaryOperatorKind::Greater
+                | BinaryOperatorKind::LessEqual
+                | BinaryOperatorKind::GreaterEqual
+                | BinaryOperatorKind::Equal
+                | BinaryOperatorKind::NotEqual
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum CursorKind {
     Unexposed(String),
     Struct(String, CodeSpan, AccessSpecifierType),
@@ -130,8 +304,9 @@ pub enum CursorKind {
         cur_type: CursorType,
         return_type: CursorType,
         canonical_return_type: CursorType,
+        attributes: FunctionAttributes,
     },
-    Variable(String, CodeSpan, CursorType),
+    Variable(String, CodeSpan, CursorType, VariableAttributes),
     Parameter(String, CodeSpan, CursorType),
     Typedef(String, CursorType, CodeSpan, AccessSpecifierType),
     Method {
@@ -142,12 +317,25 @@ pub enum CursorKind {
         virtuality: Virtuality,
         return_type: CursorType,
         canonical_return_type: CursorType,
+        attributes: FunctionAttributes,
     },
     Namespace(String, CodeSpan),
     LinkageSpec(String, CodeSpan),
-    Constructor(String, CodeSpan, ConstructorType, AccessSpecifierType),
-    Destructor(String, CodeSpan, Virtuality, AccessSpecifierType),
-    ConversionFunction(String, CodeSpan, AccessSpecifierType),
+    Constructor(
+        String,
+        CodeSpan,
+        ConstructorType,
+        AccessSpecifierType,
+        FunctionAttributes,
+    ),
+    Destructor(
+        String,
+        CodeSpan,
+        Virtuality,
+        AccessSpecifierType,
+        FunctionAttributes,
+    ),
+    ConversionFunction(String, CodeSpan, AccessSpecifierType, FunctionAttributes),
     TemplateTypeParameter(String, CodeSpan),
     TemplateNonTypeParameter(String, CodeSpan),
     TemplateTemplateParameter(String, CodeSpan),
@@ -162,28 +350,34 @@ pub enum CursorKind {
     BaseSpecifier(String, CodeSpan),
     TemplateReference(String, CodeSpan),
     NamespaceReference(String, CodeSpan),
+    /// field named in a designated initializer or a member initializer
     MemberReference(String, CodeSpan),
+    /// label of a `goto`
     LabelReference(String, CodeSpan),
+    /// set of overloads, e.g. named by a using declaration or a dependent call
     OverloadedDeclarationReference(String, CodeSpan),
+    /// variable captured by a lambda
     VariableReference(String, CodeSpan),
     UnexposedExpression(String, CodeSpan),
     DeclarationReferenceExpression(String, CodeSpan), // TODO what is this?
     MemberReferenceExpression(String, CodeSpan),
     CallExpression(String, CodeSpan), // looks like this is involved in range-based for loop
-    BlockExpression(String, CodeSpan), // TODO what is this?
-    BoolLiteral(CodeSpan),
-    IntegerLiteral(CodeSpan),
-    FloatLiteral(CodeSpan),
-    ImaginaryLiteral(String, CodeSpan), // TODO what is this?
-    StringLiteral(String, CodeSpan),
-    CharacterLiteral(String, CodeSpan),
-    UnaryOperator(CodeSpan),
+    BlockExpression(String, CodeSpan), // `^{ ... }` with `-fblocks`
+    BoolLiteral(CodeSpan, bool),
+    IntegerLiteral(CodeSpan, IntegerValue),
+    FloatLiteral(CodeSpan, FloatValue),
+    ImaginaryLiteral(String, CodeSpan), // GNU extension, e.g. `1.0i`
+    StringLiteral(String, CodeSpan, StringValue),
+    CharacterLiteral(String, CodeSpan, CharacterValue),
+    /// the operand is the only child
+    UnaryOperator(CodeSpan, UnaryOperatorKind),
     ArraySubscription(String, CodeSpan),
-    BinaryOperator(CodeSpan),
-    CompoundAssignOperator(String, CodeSpan),
+    /// the children are the left and the right operand, in this order
+    BinaryOperator(CodeSpan, BinaryOperatorKind),
+    CompoundAssignOperator(String, CodeSpan, BinaryOperatorKind),
     ConditionalOperator(String, CodeSpan),
     CStyleCast(String, CodeSpan),
-    CompoundLiteralExpression(String, CodeSpan), // TODO what is this?
+    CompoundLiteralExpression(String, CodeSpan), // e.g. `(struct point){ 1, 2 }`
     InitializerListExpression(String, CodeSpan),
     CompoundStatement(CodeSpan),
     ReturnStatement(CodeSpan),
@@ -198,6 +392,36 @@ pub enum CursorKind {
     RangeBasedForStatement(CodeSpan),
     ContinueStatement(CodeSpan),
     DeclarationStatement(CodeSpan),
+    LabelStatement(String, CodeSpan),
+    GotoStatement(CodeSpan),
+    IndirectGotoStatement(CodeSpan), // `goto *address;`, GNU extension
+    NullStatement(CodeSpan),
+    AsmStatement(CodeSpan),
+    TryStatement(CodeSpan),
+    CatchStatement(CodeSpan),
+    CoreturnStatement(CodeSpan),
+    ParenthesizedExpression(CodeSpan),
+    // casts with the type cast to
+    StaticCast(CodeSpan, CursorType),
+    DynamicCast(CodeSpan, CursorType),
+    ReinterpretCast(CodeSpan, CursorType),
+    ConstCast(CodeSpan, CursorType),
+    FunctionalCast(CodeSpan, CursorType), // `T(x)`
+    TypeidExpression(CodeSpan),
+    NullptrLiteral(CodeSpan),
+    ThisExpression(CodeSpan),
+    ThrowExpression(CodeSpan),
+    NewExpression(CodeSpan),
+    DeleteExpression(CodeSpan),
+    SizeOf(CodeSpan),
+    AlignOf(CodeSpan),
+    NoexceptExpression(CodeSpan),
+    PackExpansion(CodeSpan),
+    SizeOfPack(String, CodeSpan), // `sizeof...(pack)` with the name of the pack
+    FoldExpression(CodeSpan),
+    LambdaExpression(CodeSpan),
+    CoawaitExpression(CodeSpan),
+    CoyieldExpression(CodeSpan),
     NotSupported(String, CodeSpan, i32),
     Root,
 }
@@ -222,7 +446,7 @@ fn get_canonical_type(cur_type: CXType) -> i32 {
     unsafe { clang_getCanonicalType(cur_type).kind }
 }
 
-fn convert_into_owned(clang_string: CXString) -> String {
+pub(crate) fn convert_into_owned(clang_string: CXString) -> String {
     unsafe {
         let string = clang_getCString(clang_string);
         let string = CStr::from_ptr(string).to_string_lossy().into_owned();
@@ -231,11 +455,11 @@ fn convert_into_owned(clang_string: CXString) -> String {
     }
 }
 
-fn get_cursor_spelling(cursor: CXCursor) -> String {
+pub(crate) fn get_cursor_spelling(cursor: CXCursor) -> String {
     unsafe { convert_into_owned(clang_getCursorSpelling(cursor)) }
 }
 
-fn get_cursor_display_name(cursor: CXCursor) -> String {
+pub(crate) fn get_cursor_display_name(cursor: CXCursor) -> String {
     unsafe { convert_into_owned(clang_getCursorDisplayName(cursor)) }
 }
 
@@ -273,43 +497,306 @@ fn get_constructor_type(cursor: CXCursor) -> ConstructorType {
     }
 }
 
-fn get_cursor_extent(cursor: CXCursor) -> CodeSpan {
+pub(crate) fn get_position(location: CXSourceLocation) -> Position {
     unsafe {
-        let extent = clang_getCursorExtent(cursor);
-        let begin = clang_getRangeStart(extent);
-        let end = clang_getRangeEnd(extent);
-        let mut begin_line: u32 = 0;
-        let mut begin_col: u32 = 0;
-        let mut begin_file: CXFile = ptr::null_mut();
-        let mut end_line: u32 = 0;
-        let mut end_col: u32 = 0;
-        let mut end_file: CXFile = ptr::null_mut();
-        clang_getSpellingLocation(
-            begin,
-            &mut begin_file,
-            &mut begin_line,
-            &mut begin_col,
-            ptr::null_mut(),
+        let mut line: u32 = 0;
+        let mut col: u32 = 0;
+        let mut file: CXFile = ptr::null_mut();
+        clang_getSpellingLocation(location, &mut file, &mut line, &mut col, ptr::null_mut());
+        Position {
+            file_name: convert_into_owned(clang_getFileName(file)),
+            line,
+            col,
+        }
+    }
+}
+
+pub(crate) fn get_code_span(range: CXSourceRange) -> CodeSpan {
+    unsafe {
+        CodeSpan {
+            start_pos: get_position(clang_getRangeStart(range)),
+            end_pos: get_position(clang_getRangeEnd(range)),
+        }
+    }
+}
+
+pub(crate) fn get_cursor_extent(cursor: CXCursor) -> CodeSpan {
+    get_code_span(unsafe { clang_getCursorExtent(cursor) })
+}
+
+pub(crate) extern "C" fn collect_children(
+    current: CXCursor,
+    _parent: CXCursor,
+    client_data: *mut core::ffi::c_void,
+) -> CXChildVisitResult {
+    unsafe {
+        let children = &mut *(client_data as *mut Vec<CXCursor>);
+        children.push(current);
+    }
+    CXChildVisit_Continue
+}
+
+pub(crate) fn get_children(cursor: CXCursor) -> Vec<CXCursor> {
+    let mut children: Vec<CXCursor> = vec![];
+    unsafe {
+        clang_visitChildren(
+            cursor,
+            collect_children,
+            &mut children as *mut _ as *mut std::ffi::c_void,
         );
+    }
+    children
+}
+
+// libclang 9 exposes some expressions and statements only as unexposed cursors and has no
+// accessor telling `sizeof` from `alignof`, the tokens still tell them apart
+fn get_cursor_tokens(cursor: CXCursor) -> Vec<String> {
+    get_range_tokens(cursor, unsafe { clang_getCursorExtent(cursor) })
+}
+
+// spellings of the tokens starting in `range` of the translation unit of `cursor`, libclang
+// may add the one starting at the end of the range
+pub(crate) fn get_range_tokens(cursor: CXCursor, range: CXSourceRange) -> Vec<String> {
+    unsafe {
+        let translation_unit = clang_Cursor_getTranslationUnit(cursor);
+        let mut tokens: *mut CXToken = ptr::null_mut();
+        let mut count: u32 = 0;
+        clang_tokenize(translation_unit, range, &mut tokens, &mut count);
+        if tokens.is_null() {
+            return vec![];
+        }
+        let end = get_offset(clang_getRangeEnd(range));
+        let spellings = std::slice::from_raw_parts(tokens, count as usize)
+            .iter()
+            .filter(|token| get_offset(clang_getTokenLocation(translation_unit, **token)) < end)
+            .map(|token| convert_into_owned(clang_getTokenSpelling(translation_unit, *token)))
+            .collect();
+        clang_disposeTokens(translation_unit, tokens, count);
+        spellings
+    }
+}
+
+fn get_offset(location: CXSourceLocation) -> u32 {
+    let mut offset: u32 = 0;
+    unsafe {
         clang_getSpellingLocation(
-            end,
-            &mut end_file,
-            &mut end_line,
-            &mut end_col,
+            location,
+            ptr::null_mut(),
+            ptr::null_mut(),
             ptr::null_mut(),
+            &mut offset,
         );
-        CodeSpan {
-            start_pos: Position {
-                file_name: convert_into_owned(clang_getFileName(begin_file)),
-                line: begin_line,
-                col: begin_col,
-            },
-            end_pos: Position {
-                file_name: convert_into_owned(clang_getFileName(end_file)),
-                line: end_line,
-                col: end_col,
-            },
+    }
+    offset
+}
+
+// tokens between `children` of `cursor`, before the first one and after the last one,
+// leaving out the tokens of the children
+fn get_gap_tokens(cursor: CXCursor, children: &[CXCursor]) -> Vec<Vec<String>> {
+    unsafe {
+        let extent = clang_getCursorExtent(cursor);
+        let mut start = clang_getRangeStart(extent);
+        let mut gaps = vec![];
+        for &child in children {
+            let child_extent = clang_getCursorExtent(child);
+            gaps.push(get_range_tokens(
+                cursor,
+                clang_getRange(start, clang_getRangeStart(child_extent)),
+            ));
+            start = clang_getRangeEnd(child_extent);
+        }
+        gaps.push(get_range_tokens(
+            cursor,
+            clang_getRange(start, clang_getRangeEnd(extent)),
+        ));
+        gaps
+    }
+}
+
+// tokens of `cursor` before its first child, usually none or a keyword
+fn get_leading_tokens(cursor: CXCursor, children: &[CXCursor]) -> Vec<String> {
+    unsafe {
+        let extent = clang_getCursorExtent(cursor);
+        let end = match children.first() {
+            Some(child) => clang_getRangeStart(clang_getCursorExtent(*child)),
+            None => clang_getRangeEnd(extent),
+        };
+        get_range_tokens(cursor, clang_getRange(clang_getRangeStart(extent), end))
+    }
+}
+
+// `( pack op ... )`, `( ... op pack )` or `( init op ... op pack )`, the operands are
+// children, so the `...` is between them
+fn is_fold_expression(gaps: &[Vec<String>]) -> bool {
+    gaps.first().and_then(|gap| gap.first()).map(String::as_str) == Some("(")
+        && gaps.last().and_then(|gap| gap.last()).map(String::as_str) == Some(")")
+        && gaps.iter().flatten().any(|token| token == "...")
+}
+
+// literals expanded from a macro tokenize to the macro name, libclang evaluates those
+fn evaluate<T>(
+    cursor: CXCursor,
+    kind: CXEvalResultKind,
+    get: unsafe fn(CXEvalResult) -> T,
+) -> Option<T> {
+    unsafe {
+        let result = clang_Cursor_Evaluate(cursor);
+        if result.is_null() {
+            return None;
+        }
+        let value = if clang_EvalResult_getKind(result) == kind {
+            Some(get(result))
+        } else {
+            None
+        };
+        clang_EvalResult_dispose(result);
+        value
+    }
+}
+
+fn get_literal_token(cursor: CXCursor) -> Option<String> {
+    get_cursor_tokens(cursor)
+        .into_iter()
+        .next()
+        .filter(|token| token.starts_with(|c: char| c.is_ascii_digit() || c == '.'))
+}
+
+fn get_integer_value(cursor: CXCursor) -> IntegerValue {
+    match get_literal_token(cursor) {
+        Some(token) => parse_integer(&token),
+        None => IntegerValue {
+            spelling: String::new(),
+            value: evaluate(cursor, CXEval_Int, clang_EvalResult_getAsUnsigned).map(u128::from),
+            radix: 10,
+            suffix: String::new(),
+        },
+    }
+}
+
+fn get_float_value(cursor: CXCursor) -> FloatValue {
+    match get_literal_token(cursor) {
+        Some(token) => parse_float(&token),
+        None => FloatValue {
+            spelling: String::new(),
+            value: evaluate(cursor, CXEval_Float, clang_EvalResult_getAsDouble),
+            suffix: String::new(),
+        },
+    }
+}
+
+fn get_bool_value(cursor: CXCursor) -> bool {
+    match get_cursor_tokens(cursor).first().map(String::as_str) {
+        Some("true") => true,
+        Some("false") => false,
+        _ => {
+            evaluate(cursor, CXEval_Int, clang_EvalResult_getAsInt).is_some_and(|value| value != 0)
+        }
+    }
+}
+
+fn get_string_value(cursor: CXCursor, spelling: &str) -> StringValue {
+    let tokens = get_cursor_tokens(cursor);
+    if !tokens.is_empty() && tokens.iter().all(|token| is_quoted(token, '"')) {
+        parse_string(&tokens)
+    } else {
+        // the spelling is the literal clang built, with escapes, but never raw
+        parse_string(&[spelling.to_owned()])
+    }
+}
+
+fn get_character_value(cursor: CXCursor) -> CharacterValue {
+    let tokens = get_cursor_tokens(cursor);
+    match tokens.first() {
+        Some(token) if is_quoted(token, '\'') => parse_character(token),
+        _ => {
+            let encoding = match unsafe { clang_getCursorType(cursor).kind } {
+                clang_sys::CXType_WChar => Encoding::Wide,
+                clang_sys::CXType_Char16 => Encoding::Utf16,
+                clang_sys::CXType_Char32 => Encoding::Utf32,
+                _ => Encoding::Ordinary,
+            };
+            let value = evaluate(cursor, CXEval_Int, clang_EvalResult_getAsInt).unwrap_or(0);
+            // plain `char` may be signed, the token gives the byte
+            let value = match encoding {
+                Encoding::Ordinary if (-128..0).contains(&value) => value & 0xff,
+                _ => value,
+            };
+            CharacterValue {
+                encoding,
+                code_point: value as u32,
+            }
+        }
+    }
+}
+
+// the operator is the first token after the left operand
+fn get_binary_operator_kind(cursor: CXCursor) -> BinaryOperatorKind {
+    match get_gap_tokens(cursor, &get_children(cursor))
+        .get(1)
+        .and_then(|gap| gap.first())
+    {
+        Some(token) => BinaryOperatorKind::from_token(token),
+        None => BinaryOperatorKind::NotSupported(String::new()),
+    }
+}
+
+// postfix operators start where their operand starts
+fn get_unary_operator_kind(cursor: CXCursor) -> UnaryOperatorKind {
+    let tokens = get_cursor_tokens(cursor);
+    let code_span = get_cursor_extent(cursor);
+    let postfix = get_children(cursor)
+        .first()
+        .is_some_and(|operand| get_cursor_extent(*operand).start_pos == code_span.start_pos);
+    let token = if postfix {
+        tokens.last()
+    } else {
+        tokens.first()
+    };
+    match token {
+        Some(token) => UnaryOperatorKind::from_token(token, postfix),
+        None => UnaryOperatorKind::NotSupported(String::new()),
+    }
+}
+
+fn get_unexposed_expression_kind(cursor: CXCursor, spelling: String) -> CursorKind {
+    let code_span = get_cursor_extent(cursor);
+    let children = get_children(cursor);
+    // implicit conversions span exactly their only child and have no tokens of their own
+    if children.len() == 1 && get_cursor_extent(children[0]) == code_span {
+        return CursorKind::UnexposedExpression(spelling, code_span);
+    }
+    // the first token decides, only a fold expression needs the tokens between the children
+    match get_leading_tokens(cursor, &children)
+        .first()
+        .map(String::as_str)
+    {
+        Some("noexcept") => CursorKind::NoexceptExpression(code_span),
+        Some("co_await") => CursorKind::CoawaitExpression(code_span),
+        Some("co_yield") => CursorKind::CoyieldExpression(code_span),
+        Some("(") if is_fold_expression(&get_gap_tokens(cursor, &children)) => {
+            CursorKind::FoldExpression(code_span)
+        }
+        _ => CursorKind::UnexposedExpression(spelling, code_span),
+    }
+}
+
+fn get_unexposed_statement_kind(cursor: CXCursor, spelling: String) -> CursorKind {
+    let code_span = get_cursor_extent(cursor);
+    match get_cursor_tokens(cursor).first().map(String::as_str) {
+        Some("co_return") => CursorKind::CoreturnStatement(code_span),
+        _ => CursorKind::NotSupported(spelling, code_span, get_cursor_kind(cursor)),
+    }
+}
+
+// `sizeof`, `alignof` and the vendor specific ones
+fn get_unary_expression_kind(cursor: CXCursor, spelling: String) -> CursorKind {
+    let code_span = get_cursor_extent(cursor);
+    match get_cursor_tokens(cursor).first().map(String::as_str) {
+        Some("sizeof") => CursorKind::SizeOf(code_span),
+        Some("alignof") | Some("_Alignof") | Some("__alignof") | Some("__alignof__") => {
+            CursorKind::AlignOf(code_span)
         }
+        _ => CursorKind::NotSupported(spelling, code_span, get_cursor_kind(cursor)),
     }
 }
 
@@ -396,10 +883,10 @@ impl From<CXCursor> for CursorKind {
         let spelling = get_cursor_spelling(cursor);
         let cursor_kind = get_cursor_kind(cursor);
 
-        let cursor_kind = match cursor_kind {
+        match cursor_kind {
             clang_sys::CXCursor_UnexposedDecl => CursorKind::Unexposed(spelling),
             clang_sys::CXCursor_UnexposedExpr => {
-                CursorKind::UnexposedExpression(spelling, get_cursor_extent(cursor))
+                get_unexposed_expression_kind(cursor, spelling)
                 // example, if (a > 0) `a` is unexposed expression with `a` as a child
             }
             clang_sys::CXCursor_StructDecl => CursorKind::Struct(
@@ -438,11 +925,13 @@ impl From<CXCursor> for CursorKind {
                 cur_type: get_cursor_type(cursor).into(),
                 return_type: get_cursor_return_type(cursor).into(),
                 canonical_return_type: get_cursor_canonical_return_type(cursor).into(),
+                attributes: get_function_attributes(cursor),
             },
             clang_sys::CXCursor_VarDecl => CursorKind::Variable(
                 spelling,
                 get_cursor_extent(cursor),
                 get_cursor_type(cursor).into(),
+                get_variable_attributes(cursor),
             ),
             clang_sys::CXCursor_ParmDecl => CursorKind::Parameter(
                 spelling,
@@ -463,6 +952,7 @@ impl From<CXCursor> for CursorKind {
                 virtuality: get_cursor_virtuality(cursor),
                 return_type: get_cursor_return_type(cursor).into(),
                 canonical_return_type: get_cursor_canonical_return_type(cursor).into(),
+                attributes: get_function_attributes(cursor),
             },
             clang_sys::CXCursor_Namespace => {
                 CursorKind::Namespace(spelling, get_cursor_extent(cursor))
@@ -475,20 +965,23 @@ impl From<CXCursor> for CursorKind {
                 get_cursor_extent(cursor),
                 get_constructor_type(cursor),
                 get_access_specifier(cursor).into(),
+                get_function_attributes(cursor),
             ),
             clang_sys::CXCursor_Destructor => CursorKind::Destructor(
                 spelling,
                 get_cursor_extent(cursor),
                 get_cursor_virtuality(cursor),
                 get_access_specifier(cursor).into(),
+                get_function_attributes(cursor),
             ),
             clang_sys::CXCursor_ConversionFunction => CursorKind::ConversionFunction(
                 spelling,
                 get_cursor_extent(cursor),
                 get_access_specifier(cursor).into(),
+                get_function_attributes(cursor),
             ),
             clang_sys::CXCursor_IntegerLiteral => {
-                CursorKind::IntegerLiteral(get_cursor_extent(cursor))
+                CursorKind::IntegerLiteral(get_cursor_extent(cursor), get_integer_value(cursor))
             }
             clang_sys::CXCursor_TemplateTypeParameter => {
                 CursorKind::TemplateTypeParameter(spelling, get_cursor_extent(cursor))
@@ -533,17 +1026,19 @@ impl From<CXCursor> for CursorKind {
             clang_sys::CXCursor_NamespaceRef => {
                 CursorKind::NamespaceReference(spelling, get_cursor_extent(cursor))
             }
-            clang_sys::CXCursor_UnaryOperator => {
-                CursorKind::UnaryOperator(get_cursor_extent(cursor))
-            }
-            clang_sys::CXCursor_BinaryOperator => {
-                CursorKind::BinaryOperator(get_cursor_extent(cursor))
-            }
+            clang_sys::CXCursor_UnaryOperator => CursorKind::UnaryOperator(
+                get_cursor_extent(cursor),
+                get_unary_operator_kind(cursor),
+            ),
+            clang_sys::CXCursor_BinaryOperator => CursorKind::BinaryOperator(
+                get_cursor_extent(cursor),
+                get_binary_operator_kind(cursor),
+            ),
             clang_sys::CXCursor_DeclRefExpr => {
                 CursorKind::DeclarationReferenceExpression(spelling, get_cursor_extent(cursor))
             }
             clang_sys::CXCursor_FloatingLiteral => {
-                CursorKind::FloatLiteral(get_cursor_extent(cursor))
+                CursorKind::FloatLiteral(get_cursor_extent(cursor), get_float_value(cursor))
             }
             clang_sys::CXCursor_CompoundStmt => {
                 CursorKind::CompoundStatement(get_cursor_extent(cursor))
@@ -578,13 +1073,192 @@ impl From<CXCursor> for CursorKind {
                 get_cursor_extent(cursor),
             ),
             clang_sys::CXCursor_CXXBoolLiteralExpr => {
-                CursorKind::BoolLiteral(get_cursor_extent(cursor))
+                CursorKind::BoolLiteral(get_cursor_extent(cursor), get_bool_value(cursor))
             }
             clang_sys::CXCursor_CallExpr => {
                 CursorKind::CallExpression(spelling, get_cursor_extent(cursor))
             }
+            clang_sys::CXCursor_MemberRef => {
+                CursorKind::MemberReference(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_LabelRef => {
+                CursorKind::LabelReference(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_OverloadedDeclRef => {
+                CursorKind::OverloadedDeclarationReference(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_VariableRef => {
+                CursorKind::VariableReference(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_MemberRefExpr => {
+                CursorKind::MemberReferenceExpression(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_BlockExpr => {
+                CursorKind::BlockExpression(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_ImaginaryLiteral => {
+                CursorKind::ImaginaryLiteral(spelling, get_cursor_extent(cursor))
+            }
+            // the spelling is the literal as written, with quotes
+            clang_sys::CXCursor_StringLiteral => {
+                let value = get_string_value(cursor, &spelling);
+                CursorKind::StringLiteral(spelling, get_cursor_extent(cursor), value)
+            }
+            clang_sys::CXCursor_CharacterLiteral => CursorKind::CharacterLiteral(
+                spelling,
+                get_cursor_extent(cursor),
+                get_character_value(cursor),
+            ),
+            clang_sys::CXCursor_ArraySubscriptExpr => {
+                CursorKind::ArraySubscription(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CompoundAssignOperator => CursorKind::CompoundAssignOperator(
+                spelling,
+                get_cursor_extent(cursor),
+                get_binary_operator_kind(cursor),
+            ),
+            clang_sys::CXCursor_ConditionalOperator => {
+                CursorKind::ConditionalOperator(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CStyleCastExpr => {
+                CursorKind::CStyleCast(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CompoundLiteralExpr => {
+                CursorKind::CompoundLiteralExpression(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_InitListExpr => {
+                CursorKind::InitializerListExpression(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_LabelStmt => {
+                CursorKind::LabelStatement(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_GotoStmt => CursorKind::GotoStatement(get_cursor_extent(cursor)),
+            clang_sys::CXCursor_IndirectGotoStmt => {
+                CursorKind::IndirectGotoStatement(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_NullStmt => CursorKind::NullStatement(get_cursor_extent(cursor)),
+            clang_sys::CXCursor_AsmStmt | clang_sys::CXCursor_MSAsmStmt => {
+                CursorKind::AsmStatement(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CXXTryStmt => CursorKind::TryStatement(get_cursor_extent(cursor)),
+            clang_sys::CXCursor_CXXCatchStmt => {
+                CursorKind::CatchStatement(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_UnexposedStmt => get_unexposed_statement_kind(cursor, spelling),
+            clang_sys::CXCursor_ParenExpr => {
+                CursorKind::ParenthesizedExpression(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CXXStaticCastExpr => {
+                CursorKind::StaticCast(get_cursor_extent(cursor), get_cursor_type(cursor).into())
+            }
+            clang_sys::CXCursor_CXXDynamicCastExpr => {
+                CursorKind::DynamicCast(get_cursor_extent(cursor), get_cursor_type(cursor).into())
+            }
+            clang_sys::CXCursor_CXXReinterpretCastExpr => CursorKind::ReinterpretCast(
+                get_cursor_extent(cursor),
+                get_cursor_type(cursor).into(),
+            ),
+            clang_sys::CXCursor_CXXConstCastExpr => {
+                CursorKind::ConstCast(get_cursor_extent(cursor), get_cursor_type(cursor).into())
+            }
+            clang_sys::CXCursor_CXXFunctionalCastExpr => CursorKind::FunctionalCast(
+                get_cursor_extent(cursor),
+                get_cursor_type(cursor).into(),
+            ),
+            clang_sys::CXCursor_CXXTypeidExpr => {
+                CursorKind::TypeidExpression(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CXXNullPtrLiteralExpr => {
+                CursorKind::NullptrLiteral(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CXXThisExpr => {
+                CursorKind::ThisExpression(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CXXThrowExpr => {
+                CursorKind::ThrowExpression(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_CXXNewExpr => CursorKind::NewExpression(get_cursor_extent(cursor)),
+            clang_sys::CXCursor_CXXDeleteExpr => {
+                CursorKind::DeleteExpression(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_UnaryExpr => get_unary_expression_kind(cursor, spelling),
+            clang_sys::CXCursor_PackExpansionExpr => {
+                CursorKind::PackExpansion(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_SizeOfPackExpr => {
+                CursorKind::SizeOfPack(spelling, get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_LambdaExpr => {
+                CursorKind::LambdaExpression(get_cursor_extent(cursor))
+            }
+            clang_sys::CXCursor_TranslationUnit => CursorKind::Root,
             _ => CursorKind::NotSupported(spelling, get_cursor_extent(cursor), cursor_kind),
-        };
-        cursor_kind
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // gaps are separated by `|`
+    fn gaps(code: &str) -> Vec<Vec<String>> {
+        code.split('|')
+            .map(|gap| gap.split_whitespace().map(str::to_owned).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_operator_kinds() {
+        assert_eq!(
+            UnaryOperatorKind::from_token("++", true),
+            UnaryOperatorKind::PostIncrement
+        );
+        assert_eq!(
+            UnaryOperatorKind::from_token("++", false),
+            UnaryOperatorKind::PreIncrement
+        );
+        assert_eq!(
+            UnaryOperatorKind::from_token("not", false),
+            UnaryOperatorKind::LogicalNot
+        );
+        assert_eq!(
+            UnaryOperatorKind::from_token("-", true),
+            UnaryOperatorKind::NotSupported("-".to_owned())
+        );
+        assert!(UnaryOperatorKind::PostDecrement.is_increment_or_decrement());
+        assert!(!UnaryOperatorKind::Minus.is_increment_or_decrement());
+
+        assert_eq!(
+            BinaryOperatorKind::from_token("->*"),
+            BinaryOperatorKind::PointerToMemberArrow
+        );
+        assert_eq!(
+            BinaryOperatorKind::from_token("and"),
+            BinaryOperatorKind::LogicalAnd
+        );
+        assert_eq!(
+            BinaryOperatorKind::from_token("<<="),
+            BinaryOperatorKind::ShiftLeftAssign
+        );
+        assert_eq!(
+            BinaryOperatorKind::from_token("MACRO"),
+            BinaryOperatorKind::NotSupported("MACRO".to_owned())
+        );
+        assert!(BinaryOperatorKind::Assign.is_assignment());
+        assert!(BinaryOperatorKind::BitwiseOrAssign.is_assignment());
+        assert!(!BinaryOperatorKind::Equal.is_assignment());
+        assert!(BinaryOperatorKind::NotEqual.is_comparison());
+        assert!(!BinaryOperatorKind::Subtract.is_comparison());
+    }
+
+    #[test]
+    fn test_is_fold_expression() {
+        assert!(is_fold_expression(&gaps("( | + ... )")));
+        assert!(is_fold_expression(&gaps("( ... && | )")));
+        assert!(is_fold_expression(&gaps("( | + ... + | )")));
+        assert!(!is_fold_expression(&gaps("( | + | )")));
+        assert!(!is_fold_expression(&gaps("( | )")));
+        assert!(!is_fold_expression(&gaps("| ( | )")));
     }
 }
@@ -0,0 +1,227 @@
+use crate::source::translation_unit::cursor::{get_children, get_range_tokens};
+use clang_sys::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageClass {
+    None,
+    Extern,
+    Static,
+    PrivateExtern,
+    OpenCLWorkGroupLocal,
+    Auto,
+    Register,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Linkage {
+    NoLinkage,
+    Internal,
+    UniqueExternal, // external, but unique to the translation unit, e.g. through a local type
+    External,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RefQualifier {
+    None,
+    LValue, // `&`
+    RValue, // `&&`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExceptionSpecification {
+    None,
+    DynamicNone, // `throw()`
+    Dynamic,     // `throw(T)`
+    MSAny,       // `throw(...)`
+    BasicNoexcept,
+    ComputedNoexcept, // `noexcept(expression)`, which may evaluate to false
+    Unevaluated,      // implicit, e.g. of destructors, not yet needed by clang
+    Uninstantiated,
+    Unparsed,
+    NoThrow, // `__declspec(nothrow)`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThreadLocalKind {
+    None,
+    Dynamic, // `thread_local`, which allows dynamic initialization
+    Static,  // `_Thread_local` or `__thread`
+}
+
+/// Qualifiers and specifiers of a function, method, constructor, destructor or conversion
+/// function declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionAttributes {
+    /// `false` for declarations without a body
+    pub is_definition: bool,
+    pub storage_class: StorageClass,
+    pub linkage: Linkage,
+    /// declared `inline` or implicitly inline, like methods defined in their class
+    pub is_inline: bool,
+    pub is_constexpr: bool,
+    pub is_explicit: bool,
+    pub is_const: bool,
+    pub ref_qualifier: RefQualifier,
+    pub exception_specification: ExceptionSpecification,
+    pub is_defaulted: bool,
+    pub is_deleted: bool,
+    pub is_override: bool,
+    pub is_final: bool,
+}
+
+/// Qualifiers and specifiers of a variable declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariableAttributes {
+    /// `false` for `extern` declarations
+    pub is_definition: bool,
+    pub storage_class: StorageClass,
+    pub linkage: Linkage,
+    pub thread_local: ThreadLocalKind,
+    pub is_constexpr: bool,
+    /// the variable or, for arrays, its elements are `const`
+    pub is_const: bool,
+}
+
+impl ExceptionSpecification {
+    /// Whether the function is declared not to throw, `noexcept(expression)` counts as well.
+    pub fn is_noexcept(self) -> bool {
+        matches!(
+            self,
+            ExceptionSpecification::DynamicNone
+                | ExceptionSpecification::BasicNoexcept
+                | ExceptionSpecification::ComputedNoexcept
+                | ExceptionSpecification::NoThrow
+        )
+    }
+}
+
+pub(crate) fn get_function_attributes(cursor: CXCursor) -> FunctionAttributes {
+    let specifiers = get_specifier_tokens(cursor);
+    let children: Vec<i32> = get_children(cursor)
+        .into_iter()
+        .map(|child| unsafe { clang_getCursorKind(child) })
+        .collect();
+    // libclang has no query for deleted functions, their extent ends in `= delete`; functions
+    // with a body are not tokenized, it can be long
+    let has_body =
+        children.contains(&CXCursor_CompoundStmt) || children.contains(&CXCursor_CXXTryStmt);
+    let is_deleted = !has_body
+        && get_range_tokens(cursor, unsafe { clang_getCursorExtent(cursor) })
+            .ends_with(&["=".to_owned(), "delete".to_owned()]);
+    unsafe {
+        FunctionAttributes {
+            is_definition: clang_isCursorDefinition(cursor) != 0,
+            storage_class: get_storage_class(cursor),
+            linkage: get_linkage(cursor),
+            is_inline: clang_Cursor_isFunctionInlined(cursor) != 0,
+            is_constexpr: specifiers.iter().any(|token| token == "constexpr"),
+            is_explicit: specifiers.iter().any(|token| token == "explicit"),
+            is_const: clang_CXXMethod_isConst(cursor) != 0,
+            ref_qualifier: get_ref_qualifier(cursor),
+            exception_specification: get_exception_specification(cursor),
+            is_defaulted: clang_CXXMethod_isDefaulted(cursor) != 0,
+            is_deleted,
+            is_override: children.contains(&CXCursor_CXXOverrideAttr),
+            is_final: children.contains(&CXCursor_CXXFinalAttr),
+        }
+    }
+}
+
+pub(crate) fn get_variable_attributes(cursor: CXCursor) -> VariableAttributes {
+    unsafe {
+        VariableAttributes {
+            is_definition: clang_isCursorDefinition(cursor) != 0,
+            storage_class: get_storage_class(cursor),
+            linkage: get_linkage(cursor),
+            thread_local: match clang_getCursorTLSKind(cursor) {
+                clang_sys::CXTLS_Dynamic => ThreadLocalKind::Dynamic,
+                clang_sys::CXTLS_Static => ThreadLocalKind::Static,
+                _ => ThreadLocalKind::None,
+            },
+            is_constexpr: get_specifier_tokens(cursor)
+                .iter()
+                .any(|token| token == "constexpr"),
+            is_const: is_const_qualified(clang_getCursorType(cursor)),
+        }
+    }
+}
+
+// tokens before the name, `constexpr` and `explicit` have no libclang query
+fn get_specifier_tokens(cursor: CXCursor) -> Vec<String> {
+    unsafe {
+        let range = clang_getRange(
+            clang_getRangeStart(clang_getCursorExtent(cursor)),
+            clang_getCursorLocation(cursor),
+        );
+        get_range_tokens(cursor, range)
+    }
+}
+
+fn is_const_qualified(cursor_type: CXType) -> bool {
+    unsafe {
+        if clang_isConstQualifiedType(cursor_type) != 0 {
+            return true;
+        }
+        let element_type = clang_getArrayElementType(cursor_type);
+        element_type.kind != CXType_Invalid && is_const_qualified(element_type)
+    }
+}
+
+fn get_storage_class(cursor: CXCursor) -> StorageClass {
+    match unsafe { clang_Cursor_getStorageClass(cursor) } {
+        clang_sys::CX_SC_None => StorageClass::None,
+        clang_sys::CX_SC_Extern => StorageClass::Extern,
+        clang_sys::CX_SC_Static => StorageClass::Static,
+        clang_sys::CX_SC_PrivateExtern => StorageClass::PrivateExtern,
+        clang_sys::CX_SC_OpenCLWorkGroupLocal => StorageClass::OpenCLWorkGroupLocal,
+        clang_sys::CX_SC_Auto => StorageClass::Auto,
+        clang_sys::CX_SC_Register => StorageClass::Register,
+        _ => StorageClass::Invalid,
+    }
+}
+
+fn get_linkage(cursor: CXCursor) -> Linkage {
+    match unsafe { clang_getCursorLinkage(cursor) } {
+        clang_sys::CXLinkage_NoLinkage => Linkage::NoLinkage,
+        clang_sys::CXLinkage_Internal => Linkage::Internal,
+        clang_sys::CXLinkage_UniqueExternal => Linkage::UniqueExternal,
+        clang_sys::CXLinkage_External => Linkage::External,
+        _ => Linkage::Invalid,
+    }
+}
+
+fn get_ref_qualifier(cursor: CXCursor) -> RefQualifier {
+    match unsafe { clang_Type_getCXXRefQualifier(clang_getCursorType(cursor)) } {
+        clang_sys::CXRefQualifier_LValue => RefQualifier::LValue,
+        clang_sys::CXRefQualifier_RValue => RefQualifier::RValue,
+        _ => RefQualifier::None,
+    }
+}
+
+fn get_exception_specification(cursor: CXCursor) -> ExceptionSpecification {
+    match unsafe { clang_getCursorExceptionSpecificationType(cursor) } {
+        clang_sys::CXCursor_ExceptionSpecificationKind_DynamicNone => {
+            ExceptionSpecification::DynamicNone
+        }
+        clang_sys::CXCursor_ExceptionSpecificationKind_Dynamic => ExceptionSpecification::Dynamic,
+        clang_sys::CXCursor_ExceptionSpecificationKind_MSAny => ExceptionSpecification::MSAny,
+        clang_sys::CXCursor_ExceptionSpecificationKind_BasicNoexcept => {
+            ExceptionSpecification::BasicNoexcept
+        }
+        clang_sys::CXCursor_ExceptionSpecificationKind_ComputedNoexcept => {
+            ExceptionSpecification::ComputedNoexcept
+        }
+        clang_sys::CXCursor_ExceptionSpecificationKind_Unevaluated => {
+            ExceptionSpecification::Unevaluated
+        }
+        clang_sys::CXCursor_ExceptionSpecificationKind_Uninstantiated => {
+            ExceptionSpecification::Uninstantiated
+        }
+        clang_sys::CXCursor_ExceptionSpecificationKind_Unparsed => ExceptionSpecification::Unparsed,
+        clang_sys::CXCursor_ExceptionSpecificationKind_NoThrow => ExceptionSpecification::NoThrow,
+        _ => ExceptionSpecification::None,
+    }
+}
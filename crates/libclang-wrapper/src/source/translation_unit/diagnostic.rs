@@ -0,0 +1,146 @@
+use crate::source::translation_unit::cursor::{convert_into_owned, get_code_span, get_position};
+use crate::source::translation_unit::{CodeSpan, Position};
+use clang_sys::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ptr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Ignored,
+    Note,
+    Warning,
+    Error,
+    Fatal,
+}
+
+/// Suggested edit, replacing `code_span` with `replacement`. Insertions have an empty span,
+/// removals an empty replacement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixIt {
+    pub code_span: CodeSpan,
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// e.g. `Semantic Issue`
+    pub category: String,
+    /// flag enabling the diagnostic, e.g. `-Wunused-variable`, empty if there is none
+    pub option: String,
+    pub position: Position,
+    pub ranges: Vec<CodeSpan>,
+    pub fix_its: Vec<FixIt>,
+    /// notes attached to this diagnostic
+    pub children: Vec<Diagnostic>,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity >= Severity::Error
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Ignored => "ignored",
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal error",
+        };
+        f.write_str(name)
+    }
+}
+
+// the way clang prints it, without notes
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.position.file_name,
+            self.position.line,
+            self.position.col,
+            self.severity,
+            self.message
+        )?;
+        if !self.option.is_empty() {
+            write!(f, " [{}]", self.option)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<i32> for Severity {
+    fn from(severity: i32) -> Self {
+        match severity {
+            clang_sys::CXDiagnostic_Note => Severity::Note,
+            clang_sys::CXDiagnostic_Warning => Severity::Warning,
+            clang_sys::CXDiagnostic_Error => Severity::Error,
+            clang_sys::CXDiagnostic_Fatal => Severity::Fatal,
+            _ => Severity::Ignored,
+        }
+    }
+}
+
+fn get_diagnostic(diagnostic: CXDiagnostic) -> Diagnostic {
+    unsafe {
+        let ranges = (0..clang_getDiagnosticNumRanges(diagnostic))
+            .map(|index| get_code_span(clang_getDiagnosticRange(diagnostic, index)))
+            .collect();
+        let fix_its = (0..clang_getDiagnosticNumFixIts(diagnostic))
+            .map(|index| {
+                let mut range = clang_getNullRange();
+                let replacement =
+                    convert_into_owned(clang_getDiagnosticFixIt(diagnostic, index, &mut range));
+                FixIt {
+                    code_span: get_code_span(range),
+                    replacement,
+                }
+            })
+            .collect();
+        // owned by the diagnostic, not disposed separately
+        let children = get_diagnostics(clang_getChildDiagnostics(diagnostic));
+        Diagnostic {
+            severity: clang_getDiagnosticSeverity(diagnostic).into(),
+            message: convert_into_owned(clang_getDiagnosticSpelling(diagnostic)),
+            category: convert_into_owned(clang_getDiagnosticCategoryText(diagnostic)),
+            option: convert_into_owned(clang_getDiagnosticOption(diagnostic, ptr::null_mut())),
+            position: get_position(clang_getDiagnosticLocation(diagnostic)),
+            ranges,
+            fix_its,
+            children,
+        }
+    }
+}
+
+fn get_diagnostics(diagnostics: CXDiagnosticSet) -> Vec<Diagnostic> {
+    if diagnostics.is_null() {
+        return vec![];
+    }
+    unsafe {
+        (0..clang_getNumDiagnosticsInSet(diagnostics))
+            .map(|index| {
+                let diagnostic = clang_getDiagnosticInSet(diagnostics, index);
+                let result = get_diagnostic(diagnostic);
+                clang_disposeDiagnostic(diagnostic);
+                result
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn get_translation_unit_diagnostics(
+    translation_unit: CXTranslationUnit,
+) -> Vec<Diagnostic> {
+    unsafe {
+        let diagnostics = clang_getDiagnosticSetFromTU(translation_unit);
+        let result = get_diagnostics(diagnostics);
+        clang_disposeDiagnosticSet(diagnostics);
+        result
+    }
+}
@@ -0,0 +1,102 @@
+use clang_sys::*;
+use serde::{Deserialize, Serialize};
+
+/// Broad group of a cursor kind, as libclang classifies them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KindCategory {
+    Declaration,
+    Reference,
+    Expression,
+    Statement,
+    Attribute,
+    Preprocessing,
+    Other,
+}
+
+impl KindCategory {
+    pub(crate) fn of(cursor: CXCursor) -> KindCategory {
+        unsafe {
+            let kind = clang_getCursorKind(cursor);
+            if clang_isDeclaration(kind) != 0 {
+                KindCategory::Declaration
+            } else if clang_isReference(kind) != 0 {
+                KindCategory::Reference
+            } else if clang_isExpression(kind) != 0 {
+                KindCategory::Expression
+            } else if clang_isStatement(kind) != 0 {
+                KindCategory::Statement
+            } else if clang_isAttribute(kind) != 0 {
+                KindCategory::Attribute
+            } else if clang_isPreprocessing(kind) != 0 {
+                KindCategory::Preprocessing
+            } else {
+                KindCategory::Other
+            }
+        }
+    }
+}
+
+/// Which cursors become `Entry`s. A skipped cursor is skipped together with everything
+/// below it, so nothing is converted that is not returned.
+/// By default the whole tree is extracted.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExtractionOptions {
+    categories: Option<Vec<KindCategory>>,
+    stop_at_function_bodies: bool,
+    max_depth: Option<usize>,
+}
+
+impl ExtractionOptions {
+    pub fn new() -> ExtractionOptions {
+        ExtractionOptions::default()
+    }
+
+    /// Keeps only cursors of the given categories.
+    pub fn categories(&mut self, categories: &[KindCategory]) -> &mut ExtractionOptions {
+        self.categories = Some(categories.to_vec());
+        self
+    }
+
+    /// Namespaces, types, functions, their parameters and so on, but no references,
+    /// attributes or function bodies.
+    pub fn declarations_only(&mut self) -> &mut ExtractionOptions {
+        self.categories(&[KindCategory::Declaration])
+    }
+
+    /// Keeps declarations with their parameters, template arguments and initializers but not
+    /// the bodies of functions, methods and lambdas. `TUOptionsBuilder::skip_function_bodies`
+    /// also avoids parsing them, when nothing else needs them.
+    pub fn stop_at_function_bodies(&mut self) -> &mut ExtractionOptions {
+        self.stop_at_function_bodies = true;
+        self
+    }
+
+    /// Depth of the deepest entries, top level declarations have depth 1.
+    pub fn max_depth(&mut self, max_depth: usize) -> &mut ExtractionOptions {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// `depth` of `cursor`, whose parent is `parent`.
+    pub(crate) fn keeps(&self, cursor: CXCursor, parent: CXCursor, depth: usize) -> bool {
+        if self.max_depth.is_some_and(|max_depth| depth > max_depth) {
+            return false;
+        }
+        if self.stop_at_function_bodies && is_function_body(cursor, parent) {
+            return false;
+        }
+        match &self.categories {
+            Some(categories) => categories.contains(&KindCategory::of(cursor)),
+            None => true,
+        }
+    }
+}
+
+// bodies are the compound statements directly below a declaration, lambdas are expressions
+fn is_function_body(cursor: CXCursor, parent: CXCursor) -> bool {
+    unsafe {
+        let parent_kind = clang_getCursorKind(parent);
+        clang_getCursorKind(cursor) == CXCursor_CompoundStmt
+            && (clang_isDeclaration(parent_kind) != 0 || parent_kind == CXCursor_LambdaExpr)
+    }
+}
@@ -1,14 +1,15 @@
 use crate::source::compiler_instance::CompilerInstance;
 use crate::source::ParsingError;
 use clang_sys::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum DeclarationFromPHCMode {
     Include = 0,
     Exclude = 1,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum DiagnosticsMode {
     Disabled = 0,
     Enabled = 1,
@@ -16,7 +17,7 @@ pub enum DiagnosticsMode {
 
 pub struct Index {
     pub index: CXIndex,
-    _compiler: CompilerInstance,
+    compiler: CompilerInstance,
 }
 
 fn create_index(
@@ -26,7 +27,7 @@ fn create_index(
     unsafe {
         let res = clang_createIndex(phc_mode as i32, diag_mode as i32);
         if res.is_null() {
-            return Err(ParsingError::IndexCreationFailure);
+            Err(ParsingError::IndexCreationFailure)
         } else {
             Ok(res)
         }
@@ -39,16 +40,23 @@ impl Index {
         diagnostics_mode: DiagnosticsMode,
         compiler: CompilerInstance,
     ) -> Result<Index, ParsingError> {
+        compiler.activate();
         let result = Index {
             index: create_index(phc_mode, diagnostics_mode)?,
-            _compiler: compiler,
+            compiler,
         };
         Ok(result)
     }
+
+    pub(crate) fn activate(&self) {
+        self.compiler.activate();
+    }
 }
 
 impl Drop for Index {
     fn drop(&mut self) {
+        // may be dropped on another thread than the one it was created on
+        self.compiler.activate();
         unsafe {
             clang_disposeIndex(self.index);
         }
@@ -0,0 +1,435 @@
+use serde::{Deserialize, Serialize};
+
+/// Value of an `IntegerLiteral`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegerValue {
+    /// token as written, e.g. `0x1Full`
+    pub spelling: String,
+    /// `None` if the literal does not fit, or comes from a macro and could not be read
+    pub value: Option<u128>,
+    pub radix: u32,
+    /// e.g. `ull`, empty if there is none
+    pub suffix: String,
+}
+
+/// Value of a `FloatLiteral`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FloatValue {
+    /// token as written, e.g. `1.5e3f`
+    pub spelling: String,
+    /// `None` if the literal comes from a macro and could not be read
+    pub value: Option<f64>,
+    /// e.g. `f`, empty if there is none
+    pub suffix: String,
+}
+
+/// Encoding prefix of a string or character literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Encoding {
+    Ordinary,
+    Wide,  // `L`
+    Utf8,  // `u8`
+    Utf16, // `u`
+    Utf32, // `U`
+}
+
+/// Value of a `StringLiteral`, adjacent literals are concatenated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StringValue {
+    pub encoding: Encoding,
+    /// whether any of the concatenated literals is a raw string literal
+    pub raw: bool,
+    /// contents with escape sequences resolved, invalid UTF-8 is replaced
+    pub contents: String,
+}
+
+/// Value of a `CharacterLiteral`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharacterValue {
+    pub encoding: Encoding,
+    /// the code point, or code unit for escapes like `'\xff'`; multi-character literals
+    /// like `'ab'` get the value clang gives them, the bytes from left to right
+    pub code_point: u32,
+}
+
+pub(crate) fn parse_integer(spelling: &str) -> IntegerValue {
+    let literal: String = spelling.chars().filter(|c| *c != '\'').collect();
+    let lower = literal.to_ascii_lowercase();
+    let (radix, digits) = if lower.starts_with("0x") {
+        (16, &literal[2..])
+    } else if lower.starts_with("0b") {
+        (2, &literal[2..])
+    } else if literal.len() > 1
+        && literal.starts_with('0')
+        && literal.as_bytes()[1].is_ascii_digit()
+    {
+        (8, &literal[1..])
+    } else {
+        (10, &literal[..])
+    };
+    let end = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    let value = u128::from_str_radix(&digits[..end], radix).ok();
+    IntegerValue {
+        spelling: spelling.to_owned(),
+        value,
+        radix,
+        suffix: digits[end..].to_owned(),
+    }
+}
+
+pub(crate) fn parse_float(spelling: &str) -> FloatValue {
+    let literal: String = spelling.chars().filter(|c| *c != '\'').collect();
+    let (value, suffix) = if literal.to_ascii_lowercase().starts_with("0x") {
+        parse_hexadecimal_float(&literal[2..])
+    } else {
+        parse_decimal_float(&literal)
+    };
+    FloatValue {
+        spelling: spelling.to_owned(),
+        value,
+        suffix: suffix.to_owned(),
+    }
+}
+
+// digits, fraction and exponent, then the suffix
+fn parse_decimal_float(literal: &str) -> (Option<f64>, &str) {
+    let bytes = literal.as_bytes();
+    let mut end = 0;
+    while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+        end += 1;
+    }
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        end += 1;
+        if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+            end += 1;
+        }
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+    }
+    (literal[..end].parse().ok(), &literal[end..])
+}
+
+// hexadecimal digits with an optional fraction, then a binary exponent which is required
+fn parse_hexadecimal_float(literal: &str) -> (Option<f64>, &str) {
+    let exponent_start = match literal.find(['p', 'P']) {
+        Some(position) => position,
+        None => return (None, ""),
+    };
+    let mut mantissa = 0f64;
+    let mut fraction_digits = 0;
+    let mut in_fraction = false;
+    for c in literal[..exponent_start].chars() {
+        match c.to_digit(16) {
+            Some(digit) => {
+                mantissa = mantissa * 16.0 + f64::from(digit);
+                if in_fraction {
+                    fraction_digits += 1;
+                }
+            }
+            None if c == '.' => in_fraction = true,
+            None => return (None, ""),
+        }
+    }
+    let exponent = &literal[exponent_start + 1..];
+    let mut end = 0;
+    if exponent.starts_with(['+', '-']) {
+        end += 1;
+    }
+    end += exponent[end..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(exponent.len() - end);
+    let value = exponent[..end]
+        .parse::<i32>()
+        .ok()
+        .map(|exponent| mantissa * 2f64.powi(exponent - 4 * fraction_digits));
+    (value, &exponent[end..])
+}
+
+// prefix of a string or character literal token and whether it is raw
+fn split_prefix(token: &str) -> (Encoding, bool, &str) {
+    let (encoding, rest) = if let Some(rest) = token.strip_prefix("u8") {
+        (Encoding::Utf8, rest)
+    } else if let Some(rest) = token.strip_prefix('u') {
+        (Encoding::Utf16, rest)
+    } else if let Some(rest) = token.strip_prefix('U') {
+        (Encoding::Utf32, rest)
+    } else if let Some(rest) = token.strip_prefix('L') {
+        (Encoding::Wide, rest)
+    } else {
+        (Encoding::Ordinary, token)
+    };
+    match rest.strip_prefix('R') {
+        Some(rest) => (encoding, true, rest),
+        None => (encoding, false, rest),
+    }
+}
+
+// whether `token` is a string or character literal, and not e.g. the name of a macro
+// expanding to one
+pub(crate) fn is_quoted(token: &str, quote: char) -> bool {
+    split_prefix(token).2.starts_with(quote)
+}
+
+// code units of the literal between its quotes, narrow literals give bytes; raw literals
+// have no escape sequences
+fn decode(contents: &str, encoding: Encoding, raw: bool) -> Vec<u32> {
+    let narrow = matches!(encoding, Encoding::Ordinary | Encoding::Utf8);
+    let mut units = vec![];
+    let push_char = |units: &mut Vec<u32>, c: char| {
+        if narrow {
+            let mut buffer = [0; 4];
+            units.extend(c.encode_utf8(&mut buffer).bytes().map(u32::from));
+        } else {
+            units.push(c as u32);
+        }
+    };
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' || raw {
+            push_char(&mut units, c);
+            continue;
+        }
+        let escaped = match chars.next() {
+            Some(escaped) => escaped,
+            None => break,
+        };
+        let simple = match escaped {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            'a' => Some('\u{7}'),
+            'b' => Some('\u{8}'),
+            'f' => Some('\u{c}'),
+            'v' => Some('\u{b}'),
+            'e' | 'E' => Some('\u{1b}'),
+            '\\' | '\'' | '"' | '?' => Some(escaped),
+            _ => None,
+        };
+        if let Some(simple) = simple {
+            push_char(&mut units, simple);
+            continue;
+        }
+        match escaped {
+            'x' => {
+                let mut value = 0u32;
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(16)) {
+                    value = value.wrapping_mul(16).wrapping_add(digit);
+                    chars.next();
+                }
+                units.push(value);
+            }
+            'u' | 'U' => {
+                let length = if escaped == 'u' { 4 } else { 8 };
+                let mut value = 0u32;
+                for _ in 0..length {
+                    match chars.peek().and_then(|c| c.to_digit(16)) {
+                        Some(digit) => {
+                            value = value * 16 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                push_char(
+                    &mut units,
+                    char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER),
+                );
+            }
+            '0'..='7' => {
+                let mut value = escaped.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                units.push(value);
+            }
+            // unknown escapes keep the character, as clang does with a warning
+            _ => push_char(&mut units, escaped),
+        }
+    }
+    units
+}
+
+fn to_string(units: &[u32], encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Ordinary | Encoding::Utf8 => {
+            let bytes: Vec<u8> = units.iter().map(|unit| *unit as u8).collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        Encoding::Utf16 => {
+            let units: Vec<u16> = units.iter().map(|unit| *unit as u16).collect();
+            String::from_utf16_lossy(&units)
+        }
+        Encoding::Wide | Encoding::Utf32 => units
+            .iter()
+            .map(|unit| char::from_u32(*unit).unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect(),
+    }
+}
+
+// a literal may consist of several adjacent tokens, e.g. `"a" "b"`
+pub(crate) fn parse_string(tokens: &[String]) -> StringValue {
+    let mut value = StringValue {
+        encoding: Encoding::Ordinary,
+        raw: false,
+        contents: String::new(),
+    };
+    let mut units = vec![];
+    let mut pieces = vec![];
+    for token in tokens {
+        let (encoding, raw, rest) = split_prefix(token);
+        // anything after the closing quote is a user defined suffix
+        let (start, end) = match (rest.find('"'), rest.rfind('"')) {
+            (Some(start), Some(end)) if start < end => (start + 1, end),
+            _ => continue,
+        };
+        let mut contents = &rest[start..end];
+        if raw {
+            // `delimiter( ... )delimiter`
+            let delimiter = contents.find('(').unwrap_or(0);
+            contents = contents
+                .get(delimiter + 1..contents.len() - delimiter - 1)
+                .unwrap_or("");
+        }
+        if encoding != Encoding::Ordinary {
+            value.encoding = encoding;
+        }
+        value.raw |= raw;
+        pieces.push((raw, contents));
+    }
+    for (raw, contents) in pieces {
+        units.extend(decode(contents, value.encoding, raw));
+    }
+    value.contents = to_string(&units, value.encoding);
+    value
+}
+
+pub(crate) fn parse_character(token: &str) -> CharacterValue {
+    let (encoding, _, rest) = split_prefix(token);
+    let contents = match (rest.find('\''), rest.rfind('\'')) {
+        (Some(start), Some(end)) if start < end => &rest[start + 1..end],
+        _ => "",
+    };
+    let units = decode(contents, encoding, false);
+    let code_point = match encoding {
+        Encoding::Ordinary if units.len() > 1 => units
+            .iter()
+            .fold(0u32, |value, unit| (value << 8) | (unit & 0xff)),
+        _ => units.first().copied().unwrap_or(0),
+    };
+    CharacterValue {
+        encoding,
+        code_point,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        let literal = |spelling| {
+            let value = parse_integer(spelling);
+            (value.value, value.radix, value.suffix)
+        };
+        assert_eq!(literal("0"), (Some(0), 10, "".to_owned()));
+        assert_eq!(literal("42"), (Some(42), 10, "".to_owned()));
+        assert_eq!(literal("0x1Full"), (Some(31), 16, "ull".to_owned()));
+        assert_eq!(literal("017u"), (Some(15), 8, "u".to_owned()));
+        assert_eq!(literal("0b101"), (Some(5), 2, "".to_owned()));
+        assert_eq!(literal("1'000'000L"), (Some(1_000_000), 10, "L".to_owned()));
+        assert_eq!(literal("0uz"), (Some(0), 10, "uz".to_owned()));
+        assert_eq!(
+            literal("340282366920938463463374607431768211456"),
+            (None, 10, "".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_floats() {
+        let literal = |spelling| {
+            let value = parse_float(spelling);
+            (value.value, value.suffix)
+        };
+        assert_eq!(literal("1.5"), (Some(1.5), "".to_owned()));
+        assert_eq!(literal(".5f"), (Some(0.5), "f".to_owned()));
+        assert_eq!(literal("1e3L"), (Some(1000.0), "L".to_owned()));
+        assert_eq!(literal("2.5E-1"), (Some(0.25), "".to_owned()));
+        assert_eq!(literal("0x1.8p1f"), (Some(3.0), "f".to_owned()));
+        assert_eq!(literal("0X10P-2"), (Some(4.0), "".to_owned()));
+        assert_eq!(literal("1'000.0"), (Some(1000.0), "".to_owned()));
+    }
+
+    #[test]
+    fn test_quoted() {
+        assert!(is_quoted("\"a\"", '"'));
+        assert!(is_quoted("u8R\"(a)\"", '"'));
+        assert!(is_quoted("U'x'", '\''));
+        assert!(!is_quoted("PASSWORD", '"'));
+        assert!(!is_quoted("U_CHAR", '\''));
+        assert!(!is_quoted("Lx", '\''));
+    }
+
+    #[test]
+    fn test_strings() {
+        let literal = |tokens: &[&str]| {
+            parse_string(
+                &tokens
+                    .iter()
+                    .map(|token| (*token).to_owned())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        assert_eq!(
+            literal(&["\"secret\\n\""]),
+            StringValue {
+                encoding: Encoding::Ordinary,
+                raw: false,
+                contents: "secret\n".to_owned()
+            }
+        );
+        assert_eq!(
+            literal(&["\"a\"", "u8\"\\x62\\u00e9\""]),
+            StringValue {
+                encoding: Encoding::Utf8,
+                raw: false,
+                contents: "abé".to_owned()
+            }
+        );
+        assert_eq!(
+            literal(&["R\"key(C:\\path)\")key\""]),
+            StringValue {
+                encoding: Encoding::Ordinary,
+                raw: true,
+                contents: "C:\\path)\"".to_owned()
+            }
+        );
+        assert_eq!(literal(&["L\"\\101\""]).contents, "A");
+        assert_eq!(literal(&["U\"\\U0001F600\""]).encoding, Encoding::Utf32);
+        assert_eq!(literal(&["u\"x\"_s"]).contents, "x");
+    }
+
+    #[test]
+    fn test_characters() {
+        let literal = |token| {
+            let value = parse_character(token);
+            (value.encoding, value.code_point)
+        };
+        assert_eq!(literal("'a'"), (Encoding::Ordinary, 97));
+        assert_eq!(literal("'\\n'"), (Encoding::Ordinary, 10));
+        assert_eq!(literal("'\\xff'"), (Encoding::Ordinary, 255));
+        assert_eq!(literal("'ab'"), (Encoding::Ordinary, 0x6162));
+        assert_eq!(literal("L'\\u00e9'"), (Encoding::Wide, 0xe9));
+        assert_eq!(literal("U'😀'"), (Encoding::Utf32, 0x1f600));
+        assert_eq!(literal("u8'\\''"), (Encoding::Utf8, 39));
+    }
+}
@@ -0,0 +1,330 @@
+use crate::source::translation_unit::cursor::{
+    collect_children, convert_into_owned, get_cursor_display_name, get_cursor_extent,
+    get_cursor_spelling, get_position,
+};
+use crate::source::translation_unit::diagnostic::{get_translation_unit_diagnostics, Diagnostic};
+use crate::source::translation_unit::index::{DeclarationFromPHCMode, DiagnosticsMode, Index};
+use crate::source::translation_unit::reparse::{diff, Reparsed};
+use crate::source::translation_unit::{
+    get_ast, get_cursor, get_keyed_ast, is_ast_file, load, parse, reparse_translation_unit, save,
+    CodeSpan, CursorKind, CursorType, Entry, ExtractionOptions, Position, TUError, TUInfo,
+    TUOptionsBuilder, TranslationUnitWrapper, TraversalFilter, UnsavedFile, TU,
+};
+use crate::source::{ParseConfig, ParsingError};
+use clang_sys::*;
+use std::ffi::CString;
+use std::path::Path;
+use std::time::Instant;
+
+/// Parsed translation unit kept alive for follow-up queries, unlike `TU` which only holds
+/// the extracted AST.
+pub struct TranslationUnit {
+    // disposed before the index it was created from, fields drop in declaration order
+    translation_unit: TranslationUnitWrapper,
+    index: Index,
+    filter: TraversalFilter,
+    extraction: ExtractionOptions,
+    info: TUInfo,
+    // top level entries of the last parse by their key, extracted on the first reparse
+    declarations: Option<Vec<(String, Entry)>>,
+}
+
+// libclang allows using a translation unit from any thread, just not from several at once,
+// which `Cursor` borrowing it rules out
+unsafe impl Send for TranslationUnit {}
+
+/// Node of a live translation unit, valid as long as the translation unit is borrowed.
+#[derive(Clone, Copy)]
+pub struct Cursor<'tu> {
+    cursor: CXCursor,
+    translation_unit: &'tu TranslationUnit,
+}
+
+impl TranslationUnit {
+    /// Parses `file_name` with the unsaved files and libclang of `config`, `.ast` files are
+    /// loaded instead.
+    pub fn parse(
+        file_name: String,
+        phc_mode: DeclarationFromPHCMode,
+        diagnostics_mode: DiagnosticsMode,
+        command_line_args: Vec<String>,
+        options: TUOptionsBuilder,
+        config: &ParseConfig,
+    ) -> Result<TranslationUnit, TUError> {
+        let index = config
+            .compiler()
+            .and_then(|ci| Index::new(phc_mode, diagnostics_mode, ci));
+        match index {
+            Ok(index) => {
+                TranslationUnit::new(file_name, index, command_line_args, &options, config)
+            }
+            Err(error) => Err(TUError::new(error, file_name, command_line_args)),
+        }
+    }
+
+    pub(crate) fn new(
+        file_name: String,
+        index: Index,
+        command_line_args: Vec<String>,
+        options: &TUOptionsBuilder,
+        config: &ParseConfig,
+    ) -> Result<TranslationUnit, TUError> {
+        let start = Instant::now();
+        let translation_unit = if is_ast_file(&file_name) {
+            load(&file_name, &index)
+        } else {
+            parse(
+                &file_name,
+                &index,
+                &command_line_args,
+                config.unsaved(),
+                options,
+            )
+        };
+        let info = TUInfo::new(file_name, command_line_args, start.elapsed());
+        match translation_unit {
+            Ok(translation_unit) => Ok(TranslationUnit {
+                translation_unit,
+                index,
+                filter: config.filter().with_base_directory(&info.working_directory),
+                extraction: config.extraction().clone(),
+                info,
+                declarations: None,
+            }),
+            Err(error) => Err(TUError {
+                error,
+                info: Box::new(info),
+                diagnostics: vec![],
+            }),
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.info.file_name
+    }
+
+    pub fn info(&self) -> &TUInfo {
+        &self.info
+    }
+
+    pub fn root(&self) -> Cursor<'_> {
+        self.index.activate();
+        Cursor::new(self, get_cursor(self.translation_unit.translation_unit)).unwrap()
+    }
+
+    /// Innermost cursor covering `position`, `None` if the file is not part of the
+    /// translation unit.
+    pub fn cursor_at(&self, position: &Position) -> Option<Cursor<'_>> {
+        self.index.activate();
+        let file_name = CString::new(position.file_name.as_str()).ok()?;
+        unsafe {
+            let translation_unit = self.translation_unit.translation_unit;
+            let file = clang_getFile(translation_unit, file_name.as_ptr());
+            if file.is_null() {
+                return None;
+            }
+            let location = clang_getLocation(translation_unit, file, position.line, position.col);
+            Cursor::new(self, clang_getCursor(translation_unit, location))
+        }
+    }
+
+    /// Diagnostics of the last parse, including the ones in included files.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.index.activate();
+        get_translation_unit_diagnostics(self.translation_unit.translation_unit)
+    }
+
+    /// Owned snapshot of the AST, the same `TU::ast` holds.
+    pub fn ast(&self) -> Entry {
+        self.root().to_entry()
+    }
+
+    /// Writes the AST to `path`, which can be loaded again by parsing it as an `.ast` file with
+    /// the same libclang version. Parse with `TUOptionsBuilder::for_serialization` when saving
+    /// is planned.
+    pub fn save(&self, path: &Path) -> Result<(), ParsingError> {
+        self.index.activate();
+        save(&self.translation_unit, &path.to_string_lossy())
+    }
+
+    /// Parses the file again with `unsaved_files` replacing the ones given before, reusing
+    /// what did not change, e.g. the precompiled preamble when parsed with
+    /// `TUOptionsBuilder::precompiled_preamble`. libclang can not recover a translation unit
+    /// from a failed reparse, so it is consumed either way.
+    pub fn reparse(mut self, unsaved_files: &[UnsavedFile]) -> Result<Reparsed, TUError> {
+        let before = match self.declarations.take() {
+            Some(declarations) => declarations,
+            None => self.top_level_declarations().1,
+        };
+        let start = Instant::now();
+        let result =
+            reparse_translation_unit(&self.translation_unit, &self.info.file_name, unsaved_files);
+        self.info.parse_duration = start.elapsed();
+        if let Err(error) = result {
+            return Err(TUError {
+                error,
+                info: Box::new(self.info.clone()),
+                diagnostics: vec![],
+            });
+        }
+        let (ast, after) = self.top_level_declarations();
+        let changes = diff(before, after.clone());
+        self.declarations = Some(after);
+        Ok(Reparsed {
+            translation_unit: self,
+            ast,
+            changes,
+        })
+    }
+
+    // the AST and its children by their key, extracted in one pass with the same rules
+    fn top_level_declarations(&self) -> (Entry, Vec<(String, Entry)>) {
+        self.index.activate();
+        let (ast, keys) = get_keyed_ast(
+            get_cursor(self.translation_unit.translation_unit),
+            &self.filter,
+            &self.extraction,
+        );
+        let declarations = keys.into_iter().zip(ast.children.iter().cloned()).collect();
+        (ast, declarations)
+    }
+
+    /// Extracts the AST and releases the libclang resources.
+    pub fn into_tu(self) -> TU {
+        let start = Instant::now();
+        let ast = self.ast();
+        let diagnostics = self.diagnostics();
+        let mut info = self.info.clone();
+        info.parse_duration += start.elapsed();
+        TU {
+            ast,
+            diagnostics,
+            info,
+        }
+    }
+}
+
+impl Drop for TranslationUnit {
+    fn drop(&mut self) {
+        // may be dropped on another thread than the one it was created on
+        self.index.activate();
+    }
+}
+
+impl<'tu> Cursor<'tu> {
+    fn new(translation_unit: &'tu TranslationUnit, cursor: CXCursor) -> Option<Cursor<'tu>> {
+        if unsafe { clang_Cursor_isNull(cursor) } != 0 {
+            None
+        } else {
+            Some(Cursor {
+                cursor,
+                translation_unit,
+            })
+        }
+    }
+
+    pub fn kind(&self) -> CursorKind {
+        self.cursor.into()
+    }
+
+    pub fn spelling(&self) -> String {
+        get_cursor_spelling(self.cursor)
+    }
+
+    pub fn display_name(&self) -> String {
+        get_cursor_display_name(self.cursor)
+    }
+
+    /// Unified Symbol Resolution, identifies a declaration across translation units, empty for
+    /// cursors which are not declarations.
+    pub fn usr(&self) -> String {
+        unsafe { convert_into_owned(clang_getCursorUSR(self.cursor)) }
+    }
+
+    pub fn location(&self) -> Position {
+        get_position(unsafe { clang_getCursorLocation(self.cursor) })
+    }
+
+    pub fn extent(&self) -> CodeSpan {
+        get_cursor_extent(self.cursor)
+    }
+
+    pub fn cursor_type(&self) -> CursorType {
+        unsafe { clang_getCursorType(self.cursor).kind.into() }
+    }
+
+    /// Type as written in the source, e.g. `const std::string &`.
+    pub fn type_spelling(&self) -> String {
+        unsafe { convert_into_owned(clang_getTypeSpelling(clang_getCursorType(self.cursor))) }
+    }
+
+    pub fn is_in_system_header(&self) -> bool {
+        unsafe { clang_Location_isInSystemHeader(clang_getCursorLocation(self.cursor)) != 0 }
+    }
+
+    /// Direct children, including the ones from system headers.
+    pub fn children(&self) -> Vec<Cursor<'tu>> {
+        let mut children: Vec<CXCursor> = vec![];
+        unsafe {
+            clang_visitChildren(
+                self.cursor,
+                collect_children,
+                &mut children as *mut _ as *mut std::ffi::c_void,
+            );
+        }
+        children
+            .into_iter()
+            .filter_map(|cursor| Cursor::new(self.translation_unit, cursor))
+            .collect()
+    }
+
+    pub fn semantic_parent(&self) -> Option<Cursor<'tu>> {
+        Cursor::new(self.translation_unit, unsafe {
+            clang_getCursorSemanticParent(self.cursor)
+        })
+    }
+
+    /// Declaration a reference or an expression refers to.
+    pub fn referenced(&self) -> Option<Cursor<'tu>> {
+        Cursor::new(self.translation_unit, unsafe {
+            clang_getCursorReferenced(self.cursor)
+        })
+    }
+
+    pub fn definition(&self) -> Option<Cursor<'tu>> {
+        Cursor::new(self.translation_unit, unsafe {
+            clang_getCursorDefinition(self.cursor)
+        })
+    }
+
+    /// Whether the traversal filter of the translation unit keeps this cursor.
+    pub fn is_traversed(&self) -> bool {
+        self.translation_unit.filter.accepts(self.cursor)
+    }
+
+    /// Owned snapshot of the subtree, limited by the traversal filter and extraction options
+    /// of the translation unit, depths count from this cursor.
+    pub fn to_entry(&self) -> Entry {
+        let translation_unit = self.translation_unit;
+        get_ast(
+            self.cursor,
+            &translation_unit.filter,
+            &translation_unit.extraction,
+        )
+    }
+}
+
+impl PartialEq for Cursor<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { clang_equalCursors(self.cursor, other.cursor) != 0 }
+    }
+}
+
+impl std::fmt::Debug for Cursor<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cursor")
+            .field("kind", &self.kind())
+            .field("location", &self.location())
+            .finish()
+    }
+}
@@ -1,23 +1,57 @@
 pub mod cursor;
+pub mod declaration;
+pub mod diagnostic;
+pub mod extraction;
 pub mod index;
+pub mod literal;
+pub mod live;
+pub mod reparse;
+pub mod traversal;
+pub mod unsaved_file;
 
-use crate::source::ParsingError;
+use crate::source::compilation_database::absolute;
+use crate::source::{ParseConfig, Parsed, ParsingError};
 use clang_sys::*;
 pub use cursor::{
-    AccessSpecifierType, CodeSpan, ConstructorType, CursorKind, CursorType, Position,
-    TemplateArgumentKind, Virtuality,
+    AccessSpecifierType, BinaryOperatorKind, CodeSpan, ConstructorType, CursorKind, CursorType,
+    Position, TemplateArgumentKind, UnaryOperatorKind, Virtuality,
 };
+pub use declaration::{
+    ExceptionSpecification, FunctionAttributes, Linkage, RefQualifier, StorageClass,
+    ThreadLocalKind, VariableAttributes,
+};
+pub use diagnostic::{Diagnostic, FixIt, Severity};
+pub use extraction::{ExtractionOptions, KindCategory};
 use index::Index;
 use libc::c_char;
+pub use literal::{CharacterValue, Encoding, FloatValue, IntegerValue, StringValue};
+pub use live::{Cursor, TranslationUnit};
+use reparse::declaration_key;
+pub use reparse::{DeclarationChange, Reparsed};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::error::Error;
 use std::ffi::CString;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::ptr;
+use std::time::Duration;
+pub use traversal::TraversalFilter;
+pub use unsaved_file::UnsavedFile;
+use unsaved_file::UnsavedFiles;
 use ParsingError::FileNameConversionProblem;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct TUOptionsBuilder {
     resulting_options: i32,
 }
 
+impl Default for TUOptionsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TUOptionsBuilder {
     pub fn new() -> TUOptionsBuilder {
         TUOptionsBuilder {
@@ -69,17 +103,74 @@ impl TUOptionsBuilder {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Entry {
     pub current_kind: CursorKind,
     pub children: Vec<Entry>,
 }
 
+/// Where a translation unit, or the failure to produce it, comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TUInfo {
+    pub file_name: String,
+    /// arguments as passed to libclang, after adjusting
+    pub arguments: Vec<String>,
+    pub parse_duration: Duration,
+    /// directory relative paths in the arguments are resolved against
+    pub working_directory: PathBuf,
+    /// compilation database entry the translation unit was parsed from
+    pub command: Option<Parsed>,
+}
+
+impl TUInfo {
+    pub(crate) fn new(
+        file_name: String,
+        arguments: Vec<String>,
+        parse_duration: Duration,
+    ) -> TUInfo {
+        TUInfo {
+            working_directory: working_directory(&arguments),
+            file_name,
+            arguments,
+            parse_duration,
+            command: None,
+        }
+    }
+}
+
+// the last `-working-directory` argument, the current directory without one
+fn working_directory(arguments: &[String]) -> PathBuf {
+    let mut directory = None;
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        if argument == "-working-directory" {
+            directory = arguments.next().map(String::as_str);
+        } else if let Some(value) = argument.strip_prefix("-working-directory=") {
+            directory = Some(value);
+        }
+    }
+    match directory {
+        Some(directory) => PathBuf::from(absolute(Path::new(directory))),
+        None => env::current_dir().unwrap_or_default(),
+    }
+}
+
+#[derive(Debug)]
+pub struct TUError {
+    pub error: ParsingError,
+    pub info: Box<TUInfo>,
+    /// only filled by `TU::without_errors`, when parsing itself fails libclang leaves no
+    /// translation unit to take diagnostics from
+    pub diagnostics: Vec<Diagnostic>,
+}
+
 pub struct TU {
     ast: Entry,
+    diagnostics: Vec<Diagnostic>,
+    info: TUInfo,
 }
 
-struct TranslationUnitWrapper {
+pub(crate) struct TranslationUnitWrapper {
     pub translation_unit: CXTranslationUnit,
 }
 
@@ -87,46 +178,89 @@ fn get_cursor(translation_unit: CXTranslationUnit) -> CXCursor {
     unsafe { clang_getTranslationUnitCursor(translation_unit) }
 }
 
-fn get_ast(cursor: CXCursor) -> Entry {
-    let mut ast = Entry {
-        current_kind: CursorKind::Root,
+// the translation unit cursor itself becomes `CursorKind::Root`
+fn get_ast(cursor: CXCursor, filter: &TraversalFilter, options: &ExtractionOptions) -> Entry {
+    get_entry(cursor, filter, options, 0, None)
+}
+
+// the AST together with the key of each of its children, see `reparse::declaration_key`
+fn get_keyed_ast(
+    cursor: CXCursor,
+    filter: &TraversalFilter,
+    options: &ExtractionOptions,
+) -> (Entry, Vec<String>) {
+    let mut keys = vec![];
+    let ast = get_entry(cursor, filter, options, 0, Some(&mut keys));
+    (ast, keys)
+}
+
+fn get_entry(
+    cursor: CXCursor,
+    filter: &TraversalFilter,
+    options: &ExtractionOptions,
+    depth: usize,
+    keys: Option<&mut Vec<String>>,
+) -> Entry {
+    let mut visit = Visit {
+        filter,
+        options,
+        depth: depth + 1,
         children: vec![],
+        keys,
     };
     unsafe {
         clang_visitChildren(
             cursor,
             traverse_cursor,
-            &mut ast as *mut _ as *mut std::ffi::c_void,
+            &mut visit as *mut _ as *mut std::ffi::c_void,
         );
     }
-    ast
+    Entry {
+        current_kind: cursor.into(),
+        children: visit.children,
+    }
+}
+
+struct Visit<'a> {
+    filter: &'a TraversalFilter,
+    options: &'a ExtractionOptions,
+    // of the children being visited
+    depth: usize,
+    children: Vec<Entry>,
+    // filled with the keys of the children, when wanted
+    keys: Option<&'a mut Vec<String>>,
 }
 
 extern "C" fn traverse_cursor(
     current: CXCursor,
-    _parent: CXCursor,
+    parent: CXCursor,
     client_data: *mut core::ffi::c_void,
 ) -> CXChildVisitResult {
-    unsafe {
-        if clang_Location_isInSystemHeader(clang_getCursorLocation(current)) == 0 {
-            let node = &mut *(client_data as *mut Entry);
-            let mut new_node = Entry {
-                current_kind: current.into(),
-                children: vec![],
-            };
-            clang_visitChildren(
-                current,
-                traverse_cursor,
-                &mut new_node as *mut _ as *mut std::ffi::c_void,
-            );
-            node.children.push(new_node);
-        } else {
-            ()
+    let visit = unsafe { &mut *(client_data as *mut Visit) };
+    if visit.options.keeps(current, parent, visit.depth) && visit.filter.accepts(current) {
+        let entry = get_entry(current, visit.filter, visit.options, visit.depth, None);
+        visit.children.push(entry);
+        if let Some(keys) = visit.keys.as_mut() {
+            keys.push(declaration_key(current));
         }
     }
     CXChildVisit_Continue
 }
 
+// maps the `CXErrorCode` returned by parsing functions
+fn check_error(code: i32, file_name: &str) -> Result<(), ParsingError> {
+    match code {
+        clang_sys::CXError_Success => Ok(()),
+        clang_sys::CXError_Failure => Err(ParsingError::GenericFailure(file_name.to_owned())),
+        clang_sys::CXError_Crashed => Err(ParsingError::Crash(file_name.to_owned())),
+        clang_sys::CXError_InvalidArguments => {
+            Err(ParsingError::InvalidArguments(file_name.to_owned()))
+        }
+        clang_sys::CXError_ASTReadError => Err(ParsingError::ASTReadError(file_name.to_owned())),
+        _ => Err(ParsingError::UnknownError(file_name.to_owned(), code)),
+    }
+}
+
 fn parse_translation_unit(
     index: &Index,
     c_file_name: CString,
@@ -147,75 +281,212 @@ fn parse_translation_unit(
             options.build(),
             &mut translation_unit,
         );
-        match parse_code {
-            clang_sys::CXError_Success => {
-                assert!(!translation_unit.is_null());
-            }
-            clang_sys::CXError_Failure => {
-                return Err(ParsingError::GenericFailure(
-                    c_file_name.into_string().unwrap(),
-                ))
-            }
-            clang_sys::CXError_Crashed => {
-                return Err(ParsingError::Crash(c_file_name.into_string().unwrap()))
-            }
-            clang_sys::CXError_InvalidArguments => {
-                return Err(ParsingError::InvalidArguments(
-                    c_file_name.into_string().unwrap(),
-                ))
-            }
-            clang_sys::CXError_ASTReadError => {
-                return Err(ParsingError::ASTReadError(
-                    c_file_name.into_string().unwrap(),
-                ))
-            }
-            _ => return Err(ParsingError::UnknownError(parse_code)),
-        };
+        check_error(parse_code, &c_file_name.to_string_lossy())?;
+        assert!(!translation_unit.is_null());
     }
     Ok(translation_unit)
 }
 
+fn parse(
+    file_name: &str,
+    index: &Index,
+    command_line_args: &[String],
+    unsaved_files: &[UnsavedFile],
+    options: &TUOptionsBuilder,
+) -> Result<TranslationUnitWrapper, ParsingError> {
+    let c_file_name = CString::new(file_name);
+    let c_file_name = match c_file_name {
+        Ok(value) => value,
+        Err(_) => return Err(FileNameConversionProblem(file_name.to_owned())),
+    };
+    let command_line_args: Vec<_> = command_line_args
+        .iter()
+        .map(|value| CString::new(value.as_str()).unwrap())
+        .collect();
+    let mut command_line_args_char_vec: Vec<*const c_char> = vec![];
+    for arg in &command_line_args {
+        command_line_args_char_vec.push(arg.as_ptr());
+    }
+    let mut unsaved_files = UnsavedFiles::new(unsaved_files)?;
+    Ok(TranslationUnitWrapper {
+        translation_unit: parse_translation_unit(
+            index,
+            c_file_name,
+            command_line_args_char_vec,
+            unsaved_files.as_mut_ptr(),
+            unsaved_files.len(),
+            options,
+        )?,
+    })
+}
+
+/// Serialized ASTs are loaded instead of parsed.
+pub(crate) fn is_ast_file(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .is_some_and(|extension| extension == "ast")
+}
+
+fn load(file_name: &str, index: &Index) -> Result<TranslationUnitWrapper, ParsingError> {
+    let c_file_name = match CString::new(file_name) {
+        Ok(value) => value,
+        Err(_) => return Err(FileNameConversionProblem(file_name.to_owned())),
+    };
+    let mut translation_unit: CXTranslationUnit = ptr::null_mut();
+    unsafe {
+        let load_code =
+            clang_createTranslationUnit2(index.index, c_file_name.as_ptr(), &mut translation_unit);
+        check_error(load_code, file_name)?;
+        assert!(!translation_unit.is_null());
+    }
+    Ok(TranslationUnitWrapper { translation_unit })
+}
+
+fn save(translation_unit: &TranslationUnitWrapper, file_name: &str) -> Result<(), ParsingError> {
+    let c_file_name = match CString::new(file_name) {
+        Ok(value) => value,
+        Err(_) => return Err(FileNameConversionProblem(file_name.to_owned())),
+    };
+    unsafe {
+        let translation_unit = translation_unit.translation_unit;
+        let save_code = clang_saveTranslationUnit(
+            translation_unit,
+            c_file_name.as_ptr(),
+            clang_defaultSaveOptions(translation_unit),
+        );
+        match save_code {
+            clang_sys::CXSaveError_None => Ok(()),
+            _ => Err(ParsingError::ASTWriteError(file_name.to_owned())),
+        }
+    }
+}
+
+fn reparse_translation_unit(
+    translation_unit: &TranslationUnitWrapper,
+    file_name: &str,
+    unsaved_files: &[UnsavedFile],
+) -> Result<(), ParsingError> {
+    let mut unsaved_files = UnsavedFiles::new(unsaved_files)?;
+    unsafe {
+        let translation_unit = translation_unit.translation_unit;
+        let reparse_code = clang_reparseTranslationUnit(
+            translation_unit,
+            unsaved_files.len(),
+            unsaved_files.as_mut_ptr(),
+            clang_defaultReparseOptions(translation_unit),
+        );
+        check_error(reparse_code, file_name)
+    }
+}
+
+impl TUError {
+    pub(crate) fn new(error: ParsingError, file_name: String, arguments: Vec<String>) -> TUError {
+        TUError {
+            error,
+            info: Box::new(TUInfo::new(file_name, arguments, Duration::default())),
+            diagnostics: vec![],
+        }
+    }
+
+    pub(crate) fn with_command(mut self, command: Parsed) -> TUError {
+        self.info.working_directory = PathBuf::from(&command.directory);
+        self.info.command = Some(command);
+        self
+    }
+}
+
+// the error, then what is needed to reproduce it and the errors clang reported
+impl fmt::Display for TUError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        write!(f, "\n  arguments: {}", self.info.arguments.join(" "))?;
+        write!(
+            f,
+            "\n  working directory: {}",
+            self.info.working_directory.display()
+        )?;
+        for diagnostic in self.diagnostics.iter().filter(|d| d.is_error()) {
+            write!(f, "\n  {}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for TUError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 impl TU {
     pub fn new(
         file_name: String,
-        index: &Index,
+        index: Index,
         command_line_args: Vec<String>,
         options: &TUOptionsBuilder,
-    ) -> Result<TU, ParsingError> {
-        let c_file_name = CString::new(file_name.clone());
-        let c_file_name = match c_file_name {
-            Ok(value) => value,
-            Err(_) => return Err(FileNameConversionProblem(file_name)),
-        };
-        let command_line_args: Vec<_> = command_line_args
-            .into_iter()
-            .map(|value| CString::new(value).unwrap())
-            .collect();
-        let mut command_line_args_char_vec: Vec<*const c_char> = vec![];
-        for arg in &command_line_args {
-            command_line_args_char_vec.push(arg.as_ptr());
+        config: &ParseConfig,
+    ) -> Result<TU, TUError> {
+        TranslationUnit::new(file_name, index, command_line_args, options, config)
+            .map(TranslationUnit::into_tu)
+    }
+
+    pub(crate) fn from_parts(
+        ast: Entry,
+        diagnostics: Vec<Diagnostic>,
+        file_name: String,
+        arguments: Vec<String>,
+        parse_duration: Duration,
+    ) -> TU {
+        TU {
+            ast,
+            diagnostics,
+            info: TUInfo::new(file_name, arguments, parse_duration),
         }
-        let unsaved_files: *mut CXUnsavedFile = ptr::null_mut();
-        let unsaved_files_num = 0;
-        let translation_unit_wrapper = TranslationUnitWrapper {
-            translation_unit: parse_translation_unit(
-                index,
-                c_file_name,
-                command_line_args_char_vec,
-                unsaved_files,
-                unsaved_files_num,
-                &options,
-            )?,
-        };
-        let tu = TU {
-            ast: get_ast(get_cursor(translation_unit_wrapper.translation_unit)),
-        };
-        Ok(tu)
+    }
+
+    pub(crate) fn into_parts(self) -> (Entry, Vec<Diagnostic>, TUInfo) {
+        (self.ast, self.diagnostics, self.info)
+    }
+
+    pub(crate) fn with_command(mut self, command: Parsed) -> TU {
+        self.info.working_directory = PathBuf::from(&command.directory);
+        self.info.command = Some(command);
+        self
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.info.file_name
+    }
+
+    pub fn info(&self) -> &TUInfo {
+        &self.info
     }
 
     pub fn ast(&self) -> &Entry {
         &self.ast
     }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Whether any diagnostic is an error, the AST may be incomplete then.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(Diagnostic::is_error)
+    }
+
+    /// Fails with `ParsingError::CompilationErrors` and the diagnostics attached if any
+    /// diagnostic is an error, for tools which can not work with an incomplete AST.
+    pub fn without_errors(self) -> Result<TU, TUError> {
+        if !self.has_errors() {
+            return Ok(self);
+        }
+        Err(TUError {
+            error: ParsingError::CompilationErrors(self.info.file_name.clone()),
+            info: Box::new(self.info),
+            diagnostics: self.diagnostics,
+        })
+    }
 }
 
 impl Drop for TranslationUnitWrapper {
@@ -229,6 +500,93 @@ impl Drop for TranslationUnitWrapper {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_is_ast_file() {
+        assert!(is_ast_file("cache/main.cpp.ast"));
+        assert!(!is_ast_file("main.cpp"));
+        assert!(!is_ast_file("ast"));
+    }
+
+    #[test]
+    fn test_working_directory() {
+        let arguments = |arguments: &[&str]| -> Vec<String> {
+            arguments
+                .iter()
+                .map(|argument| (*argument).to_owned())
+                .collect()
+        };
+        assert_eq!(
+            working_directory(&arguments(&[
+                "-working-directory=/a",
+                "-working-directory",
+                "/b"
+            ])),
+            PathBuf::from("/b")
+        );
+        assert_eq!(
+            working_directory(&arguments(&["-I", "include", "-working-directory=/a/./b"])),
+            PathBuf::from("/a/b")
+        );
+        assert_eq!(
+            working_directory(&arguments(&["-x", "c++"])),
+            env::current_dir().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors_with_context() {
+        let position = Position {
+            file_name: "main.cpp".to_owned(),
+            line: 2,
+            col: 5,
+        };
+        let diagnostic = |severity, message: &str| Diagnostic {
+            severity,
+            message: message.to_owned(),
+            category: String::new(),
+            option: String::new(),
+            position: position.clone(),
+            ranges: vec![],
+            fix_its: vec![],
+            children: vec![],
+        };
+        let ast = Entry {
+            current_kind: CursorKind::Root,
+            children: vec![],
+        };
+        let arguments = vec!["-x".to_owned(), "c++".to_owned()];
+        let tu = TU::from_parts(
+            ast,
+            vec![diagnostic(Severity::Warning, "unused variable 'a'")],
+            "main.cpp".to_owned(),
+            arguments.clone(),
+            Duration::default(),
+        );
+        let tu = tu.without_errors().unwrap();
+        let (ast, mut diagnostics, _) = tu.into_parts();
+        diagnostics.push(diagnostic(Severity::Error, "expected ';'"));
+        let tu = TU::from_parts(
+            ast,
+            diagnostics,
+            "main.cpp".to_owned(),
+            arguments,
+            Duration::default(),
+        )
+        .with_command(Parsed {
+            args: vec![],
+            file: "main.cpp".to_owned(),
+            directory: "/project".to_owned(),
+        });
+        let error = tu.without_errors().err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`main.cpp` does not compile\n  arguments: -x c++\n  working directory: /project\n  \
+             main.cpp:2:5: error: expected ';'"
+        );
+        assert_eq!(error.error.to_string(), "`main.cpp` does not compile");
+    }
+
     #[test]
     fn test_options_builder() {
         let mut tu_options = TUOptionsBuilder::new();
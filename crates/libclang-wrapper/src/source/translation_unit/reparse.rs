@@ -0,0 +1,128 @@
+use crate::source::translation_unit::cursor::{convert_into_owned, get_cursor_spelling};
+use crate::source::translation_unit::{Entry, TranslationUnit};
+use clang_sys::*;
+use std::collections::HashMap;
+
+/// Top level declaration that differs between two parses of a translation unit.
+/// Positions are compared too, so declarations which only moved are `Modified`.
+// changes are few and short lived, boxing the entries is not worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq)]
+pub enum DeclarationChange {
+    Added(Entry),
+    Removed(Entry),
+    /// before and after the reparse
+    Modified(Entry, Entry),
+}
+
+/// Result of `TranslationUnit::reparse`.
+pub struct Reparsed {
+    pub translation_unit: TranslationUnit,
+    pub ast: Entry,
+    pub changes: Vec<DeclarationChange>,
+}
+
+// the USR when there is one, kind and spelling otherwise
+pub(crate) fn declaration_key(cursor: CXCursor) -> String {
+    let usr = unsafe { convert_into_owned(clang_getCursorUSR(cursor)) };
+    if usr.is_empty() {
+        format!(
+            "{}:{}",
+            unsafe { clang_getCursorKind(cursor) },
+            get_cursor_spelling(cursor)
+        )
+    } else {
+        usr
+    }
+}
+
+// declarations are matched by their key and by how often the key appeared before, telling
+// apart redeclarations
+pub(crate) fn diff(
+    before: Vec<(String, Entry)>,
+    after: Vec<(String, Entry)>,
+) -> Vec<DeclarationChange> {
+    let mut before: HashMap<(String, usize), (usize, Entry)> = with_occurrences(before)
+        .enumerate()
+        .map(|(position, (key, entry))| (key, (position, entry)))
+        .collect();
+    let mut changes = vec![];
+    for (key, entry) in with_occurrences(after) {
+        match before.remove(&key) {
+            None => changes.push(DeclarationChange::Added(entry)),
+            Some((_, old)) if old != entry => changes.push(DeclarationChange::Modified(old, entry)),
+            Some(_) => {}
+        }
+    }
+    // removed declarations in the order they had in the source
+    let mut removed: Vec<_> = before.into_values().collect();
+    removed.sort_by_key(|(position, _)| *position);
+    changes.extend(
+        removed
+            .into_iter()
+            .map(|(_, entry)| DeclarationChange::Removed(entry)),
+    );
+    changes
+}
+
+fn with_occurrences(
+    declarations: Vec<(String, Entry)>,
+) -> impl Iterator<Item = ((String, usize), Entry)> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    declarations.into_iter().map(move |(key, entry)| {
+        let occurrence = seen.entry(key.clone()).or_insert(0);
+        *occurrence += 1;
+        ((key, *occurrence), entry)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::translation_unit::{CodeSpan, CursorKind, Position};
+
+    fn namespace(name: &str, line: u32) -> (String, Entry) {
+        let position = Position {
+            file_name: "a.cpp".to_owned(),
+            line,
+            col: 1,
+        };
+        (
+            format!("c:@N@{}", name),
+            Entry {
+                current_kind: CursorKind::Namespace(
+                    name.to_owned(),
+                    CodeSpan {
+                        start_pos: position.clone(),
+                        end_pos: position,
+                    },
+                ),
+                children: vec![],
+            },
+        )
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = vec![namespace("a", 1), namespace("b", 2), namespace("c", 3)];
+        let after = vec![namespace("a", 1), namespace("c", 2), namespace("d", 3)];
+        assert_eq!(
+            diff(before, after),
+            vec![
+                DeclarationChange::Modified(namespace("c", 3).1, namespace("c", 2).1),
+                DeclarationChange::Added(namespace("d", 3).1),
+                DeclarationChange::Removed(namespace("b", 2).1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_redeclarations() {
+        let before = vec![namespace("a", 1)];
+        let after = vec![namespace("a", 1), namespace("a", 2)];
+        assert_eq!(
+            diff(before, after),
+            vec![DeclarationChange::Added(namespace("a", 2).1)]
+        );
+    }
+}
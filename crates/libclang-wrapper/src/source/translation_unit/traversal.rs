@@ -0,0 +1,170 @@
+use crate::source::compilation_database::{absolute, resolve_path};
+use crate::source::translation_unit::cursor::get_position;
+use crate::source::translation_unit::Position;
+use crate::source::FilePattern;
+use clang_sys::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+type PositionPredicate = Arc<dyn Fn(&Position) -> bool + Send + Sync>;
+
+/// Which files the AST is extracted from. Cursors outside of them are skipped together with
+/// everything below them, the translation unit itself is always kept.
+/// By default everything except system headers is traversed.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TraversalFilter {
+    main_file_only: bool,
+    project_roots: Vec<PathBuf>,
+    exclude: Vec<FilePattern>,
+    system_headers: bool,
+    // relative file names of the translation unit are relative to it, set once it is parsed
+    base_directory: PathBuf,
+    #[serde(skip)]
+    predicate: Option<PositionPredicate>,
+}
+
+impl Default for TraversalFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TraversalFilter {
+    pub fn new() -> TraversalFilter {
+        TraversalFilter {
+            main_file_only: false,
+            project_roots: vec![],
+            exclude: vec![],
+            system_headers: false,
+            base_directory: PathBuf::new(),
+            predicate: None,
+        }
+    }
+
+    /// Skips everything coming from included files.
+    pub fn main_file_only(&mut self) -> &mut TraversalFilter {
+        self.main_file_only = true;
+        self
+    }
+
+    /// Keeps only files below one of the project roots, relative roots are resolved against
+    /// the current directory.
+    pub fn project_root(&mut self, root: &Path) -> &mut TraversalFilter {
+        self.project_roots.push(PathBuf::from(absolute(root)));
+        self
+    }
+
+    pub fn exclude(&mut self, pattern: FilePattern) -> &mut TraversalFilter {
+        self.exclude.push(pattern);
+        self
+    }
+
+    pub fn include_system_headers(&mut self) -> &mut TraversalFilter {
+        self.system_headers = true;
+        self
+    }
+
+    /// Keeps only cursors for which `predicate` is true, in addition to the other rules.
+    pub fn predicate<F>(&mut self, predicate: F) -> &mut TraversalFilter
+    where
+        F: Fn(&Position) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    pub(crate) fn with_base_directory(&self, directory: &Path) -> TraversalFilter {
+        TraversalFilter {
+            base_directory: directory.to_path_buf(),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn has_predicate(&self) -> bool {
+        self.predicate.is_some()
+    }
+
+    pub(crate) fn accepts(&self, cursor: CXCursor) -> bool {
+        let location = unsafe { clang_getCursorLocation(cursor) };
+        if !self.system_headers && unsafe { clang_Location_isInSystemHeader(location) } != 0 {
+            return false;
+        }
+        if self.main_file_only && unsafe { clang_Location_isFromMainFile(location) } == 0 {
+            return false;
+        }
+        if self.project_roots.is_empty() && self.exclude.is_empty() && self.predicate.is_none() {
+            return true;
+        }
+        self.accepts_position(&get_position(location))
+    }
+
+    fn accepts_position(&self, position: &Position) -> bool {
+        let file = resolve_path(&self.base_directory, &position.file_name);
+        if !self.project_roots.is_empty()
+            && (position.file_name.is_empty()
+                || !self
+                    .project_roots
+                    .iter()
+                    .any(|root| Path::new(&file).starts_with(root)))
+        {
+            return false;
+        }
+        if self
+            .exclude
+            .iter()
+            .any(|pattern| pattern.matches(&file, &self.base_directory))
+        {
+            return false;
+        }
+        match &self.predicate {
+            Some(predicate) => predicate(position),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn position(file_name: &str) -> Position {
+        Position {
+            file_name: file_name.to_owned(),
+            line: 1,
+            col: 1,
+        }
+    }
+
+    #[test]
+    fn test_accepts_position() {
+        let mut filter = TraversalFilter::new().with_base_directory(&env::current_dir().unwrap());
+        assert!(filter.accepts_position(&position("/usr/include/vector")));
+        filter
+            .project_root(Path::new("src"))
+            .exclude(FilePattern::glob("src/vendor/**").unwrap())
+            .predicate(|position| position.line < 10);
+        assert!(filter.accepts_position(&position("src/main.cpp")));
+        assert!(filter.accepts_position(&position(&absolute(Path::new("src/lib/a.h")))));
+        assert!(!filter.accepts_position(&position("src/vendor/json.hpp")));
+        assert!(!filter.accepts_position(&position("include/a.h")));
+        assert!(!filter.accepts_position(&position("")));
+        assert!(!filter.accepts_position(&Position {
+            line: 10,
+            ..position("src/main.cpp")
+        }));
+
+        // relative names are resolved against the working directory of the translation unit
+        let mut filter = TraversalFilter::new().with_base_directory(Path::new("/project/build"));
+        filter
+            .project_root(Path::new("/project"))
+            .exclude(FilePattern::glob("generated/**").unwrap())
+            .exclude(FilePattern::glob("/project/third_party/**").unwrap());
+        assert!(filter.accepts_position(&position("../src/main.cpp")));
+        assert!(!filter.accepts_position(&position("../build/generated/a.h")));
+        assert!(!filter.accepts_position(&position("../third_party/json.hpp")));
+        assert!(!filter.accepts_position(&position("../../usr/include/vector")));
+        assert!(!filter.accepts_position(&position("/project/build/generated/a.h")));
+    }
+}
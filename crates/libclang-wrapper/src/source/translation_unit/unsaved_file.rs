@@ -0,0 +1,92 @@
+use crate::source::ParsingError;
+use clang_sys::CXUnsavedFile;
+use libc::c_ulong;
+use serde::{Deserialize, Serialize};
+use std::ffi::CString;
+use std::marker::PhantomData;
+
+/// In-memory contents replacing a file on disk while parsing, e.g. an unsaved editor buffer.
+/// The file does not need to exist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnsavedFile {
+    /// path as clang sees it, relative paths are resolved against the working directory
+    pub path: String,
+    pub contents: String,
+}
+
+impl UnsavedFile {
+    pub fn new(path: &str, contents: &str) -> UnsavedFile {
+        UnsavedFile {
+            path: path.to_owned(),
+            contents: contents.to_owned(),
+        }
+    }
+}
+
+// `CXUnsavedFile`s borrowing the contents, libclang needs them only during the call
+pub(crate) struct UnsavedFiles<'a> {
+    files: Vec<CXUnsavedFile>,
+    _paths: Vec<CString>,
+    _contents: PhantomData<&'a [UnsavedFile]>,
+}
+
+impl<'a> UnsavedFiles<'a> {
+    pub(crate) fn new(unsaved_files: &'a [UnsavedFile]) -> Result<UnsavedFiles<'a>, ParsingError> {
+        let paths = unsaved_files
+            .iter()
+            .map(|file| {
+                CString::new(file.path.as_str())
+                    .map_err(|_| ParsingError::FileNameConversionProblem(file.path.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let files = unsaved_files
+            .iter()
+            .zip(paths.iter())
+            .map(|(file, path)| CXUnsavedFile {
+                Filename: path.as_ptr(),
+                // not null terminated, the length is what counts
+                Contents: file.contents.as_ptr() as *const _,
+                Length: file.contents.len() as c_ulong,
+            })
+            .collect();
+        Ok(UnsavedFiles {
+            files,
+            _paths: paths,
+            _contents: PhantomData,
+        })
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut CXUnsavedFile {
+        self.files.as_mut_ptr()
+    }
+
+    pub(crate) fn len(&self) -> u32 {
+        self.files.len() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_unsaved_files() {
+        let unsaved_files = vec![
+            UnsavedFile::new("main.cpp", "int main() {}"),
+            UnsavedFile::new("empty.h", ""),
+        ];
+        let mut converted = UnsavedFiles::new(&unsaved_files).unwrap();
+        assert_eq!(converted.len(), 2);
+        let first = unsafe { &*converted.as_mut_ptr() };
+        assert_eq!(
+            unsafe { CStr::from_ptr(first.Filename) }.to_str(),
+            Ok("main.cpp")
+        );
+        assert_eq!(first.Length, 13);
+        assert!(matches!(
+            UnsavedFiles::new(&[UnsavedFile::new("a\0b.cpp", "")]),
+            Err(ParsingError::FileNameConversionProblem(_))
+        ));
+    }
+}
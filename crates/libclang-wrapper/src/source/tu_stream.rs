@@ -0,0 +1,197 @@
+use crate::source::isolation;
+use crate::source::translation_unit::index::{DeclarationFromPHCMode, DiagnosticsMode};
+use crate::source::translation_unit::{TUError, TUOptionsBuilder, TU};
+use crate::source::{
+    ArgumentsAdjuster, CancellationToken, ParseConfig, Parsed, ParsingError, Progress,
+};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::time::Instant;
+use threadpool::ThreadPool;
+
+/// Translation units of a compilation database in the order they finish parsing, each with
+/// the position of its command in the database.
+/// Workers hand each finished translation unit over directly, so at most one per worker waits
+/// to be consumed and dropping items after processing them keeps memory bounded. Dropping the stream skips the commands which
+/// have not started parsing yet.
+/// Every command is yielded exactly once, the ones whose worker panicked as
+/// `ParsingError::Crash` after the others.
+pub struct TUStream {
+    receiver: Receiver<(usize, Result<TU, TUError>)>,
+    // command and adjusted arguments of each position not yielded yet
+    pending: Vec<Option<(Parsed, Vec<String>)>>,
+    config: ParseConfig,
+    closed: CancellationToken,
+    start: Instant,
+    completed: usize,
+    total: usize,
+    _thread_pool: ThreadPool,
+}
+
+fn parse(
+    phc_mode: DeclarationFromPHCMode,
+    diagnostics_mode: DiagnosticsMode,
+    command: Parsed,
+    args: Vec<String>,
+    options: &TUOptionsBuilder,
+    config: &ParseConfig,
+) -> Result<TU, TUError> {
+    let file = command.file.clone();
+    let result = if config.is_cancelled() {
+        Err(TUError::new(
+            ParsingError::Cancelled(file),
+            command.file.clone(),
+            args,
+        ))
+    } else {
+        isolation::parse(phc_mode, diagnostics_mode, file, args, options, config)
+    };
+    match result {
+        Ok(tu) => Ok(tu.with_command(command)),
+        Err(error) => Err(error.with_command(command)),
+    }
+}
+
+impl TUStream {
+    pub(crate) fn new(
+        phc_mode: DeclarationFromPHCMode,
+        diagnostics_mode: DiagnosticsMode,
+        commands: Vec<Parsed>,
+        options: TUOptionsBuilder,
+        adjuster: &ArgumentsAdjuster,
+        config: &ParseConfig,
+    ) -> TUStream {
+        let start = Instant::now();
+        let total = commands.len();
+        let thread_pool = ThreadPool::new(config.n_workers());
+        // no buffer, a worker holds its result until it is consumed
+        let (tx, rx) = sync_channel(0);
+        let closed = CancellationToken::new();
+        let mut pending = Vec::with_capacity(total);
+        for (position, command) in commands.into_iter().enumerate() {
+            let tx = tx.clone();
+            let config = config.clone();
+            let closed = closed.clone();
+            let mut args = adjuster.adjust(command.args.clone(), &command.file);
+            // relative paths left in the command are resolved by clang against this
+            args.push(format!("-working-directory={}", command.directory));
+            pending.push(Some((command.clone(), args.clone())));
+            thread_pool.execute(move || {
+                if closed.is_cancelled() {
+                    return;
+                }
+                let result = parse(phc_mode, diagnostics_mode, command, args, &options, &config);
+                // fails only when the stream was dropped, nobody wants the result then
+                let _ = tx.send((position, result));
+            });
+        }
+        TUStream {
+            receiver: rx,
+            pending,
+            config: config.clone(),
+            closed,
+            start,
+            completed: 0,
+            total,
+            _thread_pool: thread_pool,
+        }
+    }
+
+    /// Number of commands in the stream, including the ones already consumed.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+impl Iterator for TUStream {
+    type Item = (usize, Result<TU, TUError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.completed == self.total {
+            return None;
+        }
+        // workers hold the only senders, so once all are gone the commands left are the ones
+        // whose worker panicked
+        let (position, result) = match self.receiver.recv() {
+            Ok(received) => received,
+            Err(_) => {
+                let position = self.pending.iter().position(Option::is_some)?;
+                let (command, args) = self.pending[position].take().unwrap();
+                let error = TUError::new(
+                    ParsingError::Crash(command.file.clone()),
+                    command.file.clone(),
+                    args,
+                );
+                (position, Err(error.with_command(command)))
+            }
+        };
+        self.pending[position] = None;
+        self.completed += 1;
+        let file_name = match &result {
+            Ok(tu) => tu.file_name(),
+            Err(error) => &error.info.file_name,
+        };
+        self.config.report(&Progress {
+            file_name,
+            index: position,
+            completed: self.completed,
+            total: self.total,
+            elapsed: self.start.elapsed(),
+        });
+        Some((position, result))
+    }
+}
+
+impl Drop for TUStream {
+    fn drop(&mut self) {
+        self.closed.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::sync_channel;
+
+    #[test]
+    fn test_panicked_workers_are_reported() {
+        let command = |file: &str| Parsed {
+            args: vec![],
+            file: file.to_owned(),
+            directory: "/".to_owned(),
+        };
+        let (tx, rx) = sync_channel(2);
+        let error = TUError::new(
+            ParsingError::Cancelled("b.c".to_owned()),
+            "b.c".to_owned(),
+            vec![],
+        );
+        tx.send((1, Err(error))).unwrap();
+        // the workers for `a.c` and `c.c` panicked without sending anything
+        drop(tx);
+        let stream = TUStream {
+            receiver: rx,
+            pending: ["a.c", "b.c", "c.c"]
+                .iter()
+                .map(|file| Some((command(file), vec!["-c".to_owned()])))
+                .collect(),
+            config: ParseConfig::new(),
+            closed: CancellationToken::new(),
+            start: Instant::now(),
+            completed: 0,
+            total: 3,
+            _thread_pool: ThreadPool::new(1),
+        };
+        let results: Vec<_> = stream
+            .map(|(position, result)| (position, result.err().unwrap()))
+            .collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, 1);
+        assert!(matches!(results[0].1.error, ParsingError::Cancelled(_)));
+        for (expected, (position, error)) in [0, 2].iter().zip(&results[1..]) {
+            assert_eq!(position, expected);
+            assert!(matches!(error.error, ParsingError::Crash(_)));
+            assert_eq!(error.info.arguments, vec!["-c".to_owned()]);
+            assert_eq!(error.info.command.as_ref().unwrap().directory, "/");
+        }
+    }
+}
@@ -0,0 +1,11 @@
+void f(int *p) {
+  p = nullptr;
+  p = new int;
+  delete p;
+  p = static_cast<int *>(p);
+  (void)sizeof(int);
+  (void)noexcept(p);
+  [] {};
+  throw 1;
+  try {} catch (...) {}
+}
@@ -0,0 +1,20 @@
+struct Base {
+  virtual ~Base() = default;
+  virtual void f() const;
+  virtual void g() noexcept;
+};
+struct Derived final : Base {
+  explicit Derived(int);
+  Derived(const Derived &) = delete;
+  void f() const override;
+  void g() noexcept final;
+  int h() & { return 0; }
+  int h() && { return 1; }
+  constexpr int i() const { return 2; }
+  explicit operator bool() const;
+};
+static inline int j() { return 3; }
+extern int k;
+thread_local int l = 0;
+constexpr int m = 4;
+namespace { int n; }
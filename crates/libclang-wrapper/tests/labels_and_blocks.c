@@ -0,0 +1,2 @@
+void g(void) { goto done; done:; }
+void (^b)(void) = ^{ };
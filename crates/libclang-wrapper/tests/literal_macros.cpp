@@ -0,0 +1,6 @@
+#define PASSWORD "hunter2"
+#define U_CHAR U'x'
+#define NEWLINE '\n'
+const char password[] = PASSWORD;
+char32_t c = U_CHAR;
+char n = NEWLINE;
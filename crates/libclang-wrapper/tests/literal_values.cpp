@@ -0,0 +1,7 @@
+unsigned long long a = 0x1Full;
+int b = 1'000;
+float c = 1.5e3f;
+bool d = true;
+const char e[] = u8"hi\n";
+const wchar_t f[] = LR"(a\b)";
+char32_t g = U'\x41';
@@ -0,0 +1,3 @@
+const char s[] = "hi";
+int c = 'a';
+_Complex double z = 1.0i;
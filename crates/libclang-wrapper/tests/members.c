@@ -0,0 +1,4 @@
+struct point { int x; };
+void f(struct point p) { p.x = 1; }
+struct point q = { .x = 2 };
+struct point r(void) { return (struct point){ 3 }; }
@@ -0,0 +1,4 @@
+int f(int *p, int a) {
+  p[0] += a ? 1 : 2;
+  return (int)2.5;
+}
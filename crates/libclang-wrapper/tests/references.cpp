@@ -0,0 +1,3 @@
+namespace n { void h(int); void h(double); }
+using n::h;
+int main() { int a = 0; [a] { return a; }; }
@@ -1,7 +1,7 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
-    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry, Position,
-    Source, TUOptionsBuilder,
+    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry,
+    ExceptionSpecification, FunctionAttributes, Linkage, ParseConfig, Position, RefQualifier,
+    Source, StorageClass, TUOptionsBuilder,
 };
 
 #[test]
@@ -12,10 +12,11 @@ fn test_chars() {
         DiagnosticsMode::Enabled,
         vec!["-std=c++14".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -39,7 +40,22 @@ fn test_chars() {
                     },
                     cur_type: CursorType::FunctionProto,
                     return_type: CursorType::CharS,
-                    canonical_return_type: CursorType::CharS
+                    canonical_return_type: CursorType::CharS,
+                    attributes: FunctionAttributes {
+                        is_definition: false,
+                        storage_class: StorageClass::None,
+                        linkage: Linkage::External,
+                        is_inline: false,
+                        is_constexpr: false,
+                        is_explicit: false,
+                        is_const: false,
+                        ref_qualifier: RefQualifier::None,
+                        exception_specification: ExceptionSpecification::None,
+                        is_defaulted: false,
+                        is_deleted: false,
+                        is_override: false,
+                        is_final: false,
+                    }
                 },
                 children: vec![
                     Entry {
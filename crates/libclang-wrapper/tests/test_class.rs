@@ -1,7 +1,7 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
     AccessSpecifierType, CodeSpan, ConstructorType, CursorKind, CursorType, DeclarationFromPHCMode,
-    DiagnosticsMode, Entry, Position, Source, TUOptionsBuilder, Virtuality,
+    DiagnosticsMode, Entry, ExceptionSpecification, FunctionAttributes, Linkage, ParseConfig,
+    Position, RefQualifier, Source, StorageClass, TUOptionsBuilder, Virtuality,
 };
 
 #[test]
@@ -12,10 +12,11 @@ fn parse_class_in_namespace() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -148,7 +149,22 @@ fn parse_class_in_namespace() {
                                     }
                                 },
                                 ConstructorType::Default,
-                                AccessSpecifierType::Public
+                                AccessSpecifierType::Public,
+                                FunctionAttributes {
+                                    is_definition: false,
+                                    storage_class: StorageClass::None,
+                                    linkage: Linkage::External,
+                                    is_inline: false,
+                                    is_constexpr: false,
+                                    is_explicit: false,
+                                    is_const: false,
+                                    ref_qualifier: RefQualifier::None,
+                                    exception_specification: ExceptionSpecification::None,
+                                    is_defaulted: false,
+                                    is_deleted: false,
+                                    is_override: false,
+                                    is_final: false,
+                                }
                             ),
                             children: vec![]
                         },
@@ -168,7 +184,22 @@ fn parse_class_in_namespace() {
                                     }
                                 },
                                 ConstructorType::Copy,
-                                AccessSpecifierType::Public
+                                AccessSpecifierType::Public,
+                                FunctionAttributes {
+                                    is_definition: false,
+                                    storage_class: StorageClass::None,
+                                    linkage: Linkage::External,
+                                    is_inline: false,
+                                    is_constexpr: false,
+                                    is_explicit: false,
+                                    is_const: false,
+                                    ref_qualifier: RefQualifier::None,
+                                    exception_specification: ExceptionSpecification::None,
+                                    is_defaulted: false,
+                                    is_deleted: false,
+                                    is_override: false,
+                                    is_final: false,
+                                }
                             ),
                             children: vec![Entry {
                                 current_kind: CursorKind::Parameter(
@@ -226,7 +257,22 @@ fn parse_class_in_namespace() {
                                 cur_type: CursorType::FunctionProto,
                                 virtuality: Virtuality::PureVirtual,
                                 return_type: CursorType::LValueReference,
-                                canonical_return_type: CursorType::LValueReference
+                                canonical_return_type: CursorType::LValueReference,
+                                attributes: FunctionAttributes {
+                                    is_definition: false,
+                                    storage_class: StorageClass::None,
+                                    linkage: Linkage::External,
+                                    is_inline: false,
+                                    is_constexpr: false,
+                                    is_explicit: false,
+                                    is_const: false,
+                                    ref_qualifier: RefQualifier::None,
+                                    exception_specification: ExceptionSpecification::None,
+                                    is_defaulted: false,
+                                    is_deleted: false,
+                                    is_override: false,
+                                    is_final: false,
+                                }
                             },
                             children: vec![
                                 Entry {
@@ -342,7 +388,22 @@ fn parse_class_in_namespace() {
                                 cur_type: CursorType::FunctionProto,
                                 virtuality: Virtuality::NonVirtual,
                                 return_type: CursorType::Void,
-                                canonical_return_type: CursorType::Void
+                                canonical_return_type: CursorType::Void,
+                                attributes: FunctionAttributes {
+                                    is_definition: false,
+                                    storage_class: StorageClass::None,
+                                    linkage: Linkage::External,
+                                    is_inline: false,
+                                    is_constexpr: false,
+                                    is_explicit: false,
+                                    is_const: false,
+                                    ref_qualifier: RefQualifier::None,
+                                    exception_specification: ExceptionSpecification::None,
+                                    is_defaulted: false,
+                                    is_deleted: false,
+                                    is_override: false,
+                                    is_final: false,
+                                }
                             },
                             children: vec![]
                         },
@@ -362,7 +423,22 @@ fn parse_class_in_namespace() {
                                     }
                                 },
                                 ConstructorType::Move,
-                                AccessSpecifierType::Protected
+                                AccessSpecifierType::Protected,
+                                FunctionAttributes {
+                                    is_definition: false,
+                                    storage_class: StorageClass::None,
+                                    linkage: Linkage::External,
+                                    is_inline: false,
+                                    is_constexpr: false,
+                                    is_explicit: false,
+                                    is_const: false,
+                                    ref_qualifier: RefQualifier::None,
+                                    exception_specification: ExceptionSpecification::None,
+                                    is_defaulted: false,
+                                    is_deleted: false,
+                                    is_override: false,
+                                    is_final: false,
+                                }
                             ),
                             children: vec![Entry {
                                 current_kind: CursorKind::Parameter(
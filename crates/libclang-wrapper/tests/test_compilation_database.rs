@@ -1,13 +1,71 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
-    CompilationDatabase, DeclarationFromPHCMode, DiagnosticsMode, Parsed, Source, TUOptionsBuilder,
+    discover, ArgumentsAdjuster, AutoCompilationDatabase, CancellationToken, CommandFilter,
+    CompilationDatabase, DeclarationFromPHCMode, DeduplicationPolicy, DiagnosticsMode, FilePattern,
+    FixedCompilationDatabase, JsonCompilationDatabase, ParseConfig, Parsed, ParsingError, Source,
+    TUOptionsBuilder,
 };
+use std::env;
+use std::fs;
 use std::path::Path;
+use std::process;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_compilation_database_error() {
+    fn load(file: &str) -> Result<usize, ParsingError> {
+        Ok(JsonCompilationDatabase::new(Path::new(file))?
+            .commands()
+            .len())
+    }
+    assert_eq!(load("tests/test_compile_commands.json").unwrap(), 2);
+    let error = load("tests/missing_compile_commands.json").err().unwrap();
+    assert!(matches!(error, ParsingError::CompilationDatabaseError(_)));
+    assert!(error.to_string().starts_with(
+        "could not read the compilation database: `tests/missing_compile_commands.json`: "
+    ));
+}
 
 #[test]
 fn test_compilation_database() {
+    let directory = "/some_directory/Debug/BuildInfo".to_owned();
+    let compile_database =
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands.json")).unwrap();
+    assert_eq!(
+        compile_database.commands,
+        vec![
+            Parsed {
+                args: vec![
+                    "/usr/bin/clang++-10".to_owned(),
+                    "-fno-limit-debug-info".to_owned(),
+                    "-fPIC".to_owned(),
+                    "-x".to_owned(),
+                    "c++".to_owned(),
+                    "-std=c++14".to_owned()
+                ],
+                file: format!("{}/tests/class.h", directory),
+                directory: directory.clone()
+            },
+            Parsed {
+                args: vec![
+                    "/usr/bin/clang++-10".to_owned(),
+                    "-fno-limit-debug-info".to_owned(),
+                    "-fPIC".to_owned(),
+                    "-std=gnu++14".to_owned()
+                ],
+                file: format!("{}/tests/header.h", directory),
+                directory: directory.clone()
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_compilation_database_relative_directory() {
+    let directory = env!("CARGO_MANIFEST_DIR").to_owned();
+    // relative directories are relative to the compilation database itself
     let compile_database =
-        CompilationDatabase::new(Path::new("tests/test_compile_commands.json")).unwrap();
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_relative.json"))
+            .unwrap();
     assert_eq!(
         compile_database.commands,
         vec![
@@ -20,7 +78,8 @@ fn test_compilation_database() {
                     "c++".to_owned(),
                     "-std=c++14".to_owned()
                 ],
-                file: "./tests/class.h".to_owned()
+                file: format!("{}/tests/class.h", directory),
+                directory: directory.clone()
             },
             Parsed {
                 args: vec![
@@ -29,21 +88,211 @@ fn test_compilation_database() {
                     "-fPIC".to_owned(),
                     "-std=gnu++14".to_owned()
                 ],
-                file: "./tests/header.h".to_owned()
+                file: format!("{}/tests/header.h", directory),
+                directory: directory.clone()
             }
         ]
     );
 }
 
+#[test]
+fn test_compilation_database_arguments_and_quoting() {
+    let directory = env!("CARGO_MANIFEST_DIR").to_owned();
+    let compile_database =
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_arguments.json"))
+            .unwrap();
+    assert_eq!(
+        compile_database.commands,
+        vec![
+            Parsed {
+                args: vec![
+                    "/usr/bin/clang++-10".to_owned(),
+                    "-DNAME=\"a b\"".to_owned(),
+                    "-x".to_owned(),
+                    "c++".to_owned(),
+                    "-std=c++14".to_owned()
+                ],
+                file: format!("{}/tests/class.h", directory),
+                directory: directory.clone()
+            },
+            Parsed {
+                args: vec![
+                    "/usr/bin/clang++-10".to_owned(),
+                    "-DNAME=a b".to_owned(),
+                    "-DQUOTED=\"x\"".to_owned(),
+                    format!("-I{}/include dir", directory),
+                    "-std=gnu++14".to_owned()
+                ],
+                file: format!("{}/tests/header.h", directory),
+                directory: directory.clone()
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_compilation_database_without_command() {
+    assert!(
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_invalid.json"))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_command_for_files_missing_from_database() {
+    let directory = env!("CARGO_MANIFEST_DIR").to_owned();
+    let compile_database =
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_relative.json"))
+            .unwrap();
+    assert_eq!(
+        compile_database.command_for(Path::new("tests/class.h")),
+        compile_database.commands.first().cloned()
+    );
+    assert_eq!(
+        compile_database.for_files(&[Path::new("tests/type_aliases_and_typedefs.cpp")]),
+        vec![Parsed {
+            args: vec![
+                "/usr/bin/clang++-10".to_owned(),
+                "-x".to_owned(),
+                "c++".to_owned(),
+                "-fno-limit-debug-info".to_owned(),
+                "-fPIC".to_owned(),
+                "-std=c++14".to_owned()
+            ],
+            file: format!("{}/tests/type_aliases_and_typedefs.cpp", directory),
+            directory: directory.clone()
+        }]
+    );
+}
+
+#[test]
+fn test_fixed_compilation_database() {
+    let directory = env!("CARGO_MANIFEST_DIR").to_owned();
+    let compile_database = FixedCompilationDatabase::new(
+        Path::new("tests"),
+        vec!["-x".to_owned(), "c++".to_owned(), "-Iinclude".to_owned()],
+        &[Path::new("tests/header.h"), Path::new("tests/class.h")],
+    );
+    let command = |file: &str| Parsed {
+        args: vec![
+            "clang-tool".to_owned(),
+            "-x".to_owned(),
+            "c++".to_owned(),
+            format!("-I{}/tests/include", directory),
+            format!("{}/tests/{}", directory, file),
+        ],
+        file: format!("{}/tests/{}", directory, file),
+        directory: format!("{}/tests", directory),
+    };
+    assert_eq!(
+        compile_database.commands(),
+        vec![command("header.h"), command("class.h")]
+    );
+    assert_eq!(
+        compile_database.command_for(Path::new("tests/for.cpp")),
+        Some(command("for.cpp"))
+    );
+}
+
+#[test]
+fn test_compilation_database_discovery() {
+    let directory = env!("CARGO_MANIFEST_DIR").to_owned();
+    let flags_database = FixedCompilationDatabase::from_flags_file(
+        Path::new("tests/discovery/compile_flags.txt"),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(flags_database.commands(), vec![]);
+    let discovered = discover(Path::new("tests/discovery/src/main.cpp")).unwrap();
+    let expected = Some(Parsed {
+        args: vec![
+            "clang-tool".to_owned(),
+            "-x".to_owned(),
+            "c++".to_owned(),
+            "-std=c++17".to_owned(),
+            format!("-I{}/tests/discovery/include", directory),
+            format!("{}/tests/discovery/src/main.cpp", directory),
+        ],
+        file: format!("{}/tests/discovery/src/main.cpp", directory),
+        directory: format!("{}/tests/discovery", directory),
+    });
+    assert_eq!(
+        flags_database.command_for(Path::new("tests/discovery/src/main.cpp")),
+        expected
+    );
+    assert_eq!(
+        discovered.command_for(Path::new("tests/discovery/src/main.cpp")),
+        expected
+    );
+
+    // like for a `compile_commands.json`, only what the database lists
+    assert_eq!(discovered.commands(), vec![]);
+    assert_eq!(
+        discovered.for_files(&[Path::new("tests/discovery/src/main.cpp")]),
+        vec![expected.clone().unwrap()]
+    );
+    let discovered = discover(Path::new("tests/discovery/json/src/main.cpp")).unwrap();
+    assert_eq!(discovered.commands().len(), 1);
+
+    let auto_database = AutoCompilationDatabase::new(&[
+        Path::new("tests/discovery/json/src/main.cpp"),
+        Path::new("/no_database_here.cpp"),
+        Path::new("tests/discovery/src/main.cpp"),
+    ]);
+    assert_eq!(
+        auto_database.command_for(Path::new("tests/discovery/src/main.cpp")),
+        expected
+    );
+    assert_eq!(
+        auto_database.command_for(Path::new("tests/discovery/json/src/main.cpp")),
+        Some(Parsed {
+            args: vec!["/usr/bin/clang++".to_owned(), "-std=c++14".to_owned()],
+            file: format!("{}/tests/discovery/json/src/main.cpp", directory),
+            directory: format!("{}/tests/discovery/json", directory),
+        })
+    );
+    // in the order given, files without a database are left out
+    let commands = auto_database.commands();
+    assert_eq!(commands.len(), 2);
+    assert_eq!(
+        commands[0].file,
+        format!("{}/tests/discovery/json/src/main.cpp", directory)
+    );
+    assert_eq!(Some(commands[1].clone()), expected);
+    let empty = env::temp_dir().join(format!("libclang_wrapper_{}_no_database", process::id()));
+    fs::create_dir_all(&empty).unwrap();
+    assert!(discover(&empty).is_err());
+    fs::remove_dir(&empty).unwrap();
+}
+
+#[test]
+fn test_filtered_compilation_database() {
+    let compile_database =
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_relative.json"))
+            .unwrap();
+    let mut filter = CommandFilter::new();
+    filter
+        .include(FilePattern::glob("tests/*.h").unwrap())
+        .exclude(FilePattern::regex(r"header\.h$").unwrap())
+        .deduplicate(DeduplicationPolicy::First);
+    assert_eq!(
+        compile_database.filtered(&filter),
+        vec![compile_database.commands[0].clone()]
+    );
+}
+
 #[test]
 fn test_parsing_with_compilation_database() {
     let compile_database =
-        CompilationDatabase::new(Path::new("tests/test_compile_commands.json")).unwrap();
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_relative.json"))
+            .unwrap();
     let source = Source::from_compilation_database(
         DeclarationFromPHCMode::Exclude,
         DiagnosticsMode::Enabled,
-        compile_database,
+        &compile_database,
         TUOptionsBuilder::new(),
+        &ArgumentsAdjuster::standard(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let (success, errors): (Vec<_>, Vec<_>) = source
@@ -53,3 +302,88 @@ fn test_parsing_with_compilation_database() {
     assert_eq!(success.len(), 2);
     assert_eq!(errors.len(), 0);
 }
+
+#[test]
+fn test_cancelled_parsing_with_compilation_database() {
+    let compile_database =
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_relative.json"))
+            .unwrap();
+    let token = CancellationToken::new();
+    token.cancel();
+    let reported = Arc::new(Mutex::new(vec![]));
+    let mut config = ParseConfig::new();
+    {
+        let reported = reported.clone();
+        config
+            .workers(2)
+            .cancellation_token(token)
+            .progress(move |progress| {
+                reported
+                    .lock()
+                    .unwrap()
+                    .push((progress.index, progress.completed, progress.total))
+            });
+    }
+    let source = Source::from_compilation_database(
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        &compile_database,
+        TUOptionsBuilder::new(),
+        &ArgumentsAdjuster::standard(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(source.translation_units.len(), 2);
+    for (result, command) in source
+        .translation_units
+        .iter()
+        .zip(compile_database.commands.iter())
+    {
+        let error = result.as_ref().err().unwrap();
+        assert!(matches!(error.error, ParsingError::Cancelled(_)));
+        assert_eq!(error.info.file_name, command.file);
+        assert_eq!(error.info.command.as_ref(), Some(command));
+        assert_eq!(
+            error.info.arguments.last().unwrap(),
+            &format!("-working-directory={}", command.directory)
+        );
+    }
+    let mut reported = reported.lock().unwrap().clone();
+    reported.sort();
+    assert_eq!(reported.len(), 2);
+    assert!(reported
+        .iter()
+        .all(|(index, _, total)| *index < 2 && *total == 2));
+}
+
+#[test]
+fn test_streaming_compilation_database() {
+    let compile_database =
+        JsonCompilationDatabase::new(Path::new("tests/test_compile_commands_relative.json"))
+            .unwrap();
+    let token = CancellationToken::new();
+    token.cancel();
+    let mut config = ParseConfig::new();
+    config.workers(1).cancellation_token(token);
+    let stream = Source::stream_compilation_database(
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        &compile_database,
+        TUOptionsBuilder::new(),
+        &ArgumentsAdjuster::standard(),
+        &config,
+    );
+    assert_eq!(stream.total(), 2);
+    let mut positions: Vec<_> = stream
+        .map(|(position, result)| {
+            let error = result.err().unwrap();
+            assert_eq!(
+                error.info.file_name,
+                compile_database.commands[position].file
+            );
+            position
+        })
+        .collect();
+    positions.sort();
+    assert_eq!(positions, vec![0, 1]);
+}
@@ -0,0 +1,31 @@
+use libclang_wrapper::source::{CompilerInstance, ParsingError};
+use std::path::Path;
+use std::thread;
+
+#[test]
+fn test_missing_library() {
+    let result = CompilerInstance::from_path(Path::new("tests/no_libclang_here"));
+    assert!(matches!(result, Err(ParsingError::LibraryLoadFailure(_))));
+}
+
+#[test]
+fn test_shared_instance_across_threads() {
+    let compiler_instance = CompilerInstance::shared().unwrap();
+    assert!(compiler_instance.version().is_some());
+    let path = compiler_instance.path().to_owned();
+    let handles: Vec<_> = (0..4)
+        .map(|_| thread::spawn(|| CompilerInstance::shared().unwrap().path().to_owned()))
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), path);
+    }
+    let from_path = CompilerInstance::from_path(&path).unwrap();
+    assert_eq!(from_path.path(), path);
+}
+
+#[test]
+fn test_from_path_directory() {
+    let path = CompilerInstance::shared().unwrap().path().to_owned();
+    let compiler_instance = CompilerInstance::from_path(path.parent().unwrap()).unwrap();
+    assert_eq!(compiler_instance.path().parent(), path.parent());
+}
@@ -0,0 +1,150 @@
+use libclang_wrapper::source::{
+    CursorKind, DeclarationFromPHCMode, DiagnosticsMode, Entry, ExceptionSpecification,
+    FunctionAttributes, Linkage, ParseConfig, RefQualifier, Source, StorageClass, TUOptionsBuilder,
+    ThreadLocalKind, VariableAttributes,
+};
+use std::collections::HashMap;
+
+// attributes of the declarations in the tree, by the line they start on
+fn collect(
+    entry: &Entry,
+    functions: &mut HashMap<u32, FunctionAttributes>,
+    variables: &mut HashMap<u32, VariableAttributes>,
+) {
+    match &entry.current_kind {
+        CursorKind::Function {
+            code_span,
+            attributes,
+            ..
+        }
+        | CursorKind::Method {
+            code_span,
+            attributes,
+            ..
+        }
+        | CursorKind::Constructor(_, code_span, _, _, attributes)
+        | CursorKind::Destructor(_, code_span, _, _, attributes)
+        | CursorKind::ConversionFunction(_, code_span, _, attributes) => {
+            functions.insert(code_span.start_pos.line, attributes.clone());
+        }
+        CursorKind::Variable(_, code_span, _, attributes) => {
+            variables.insert(code_span.start_pos.line, attributes.clone());
+        }
+        _ => {}
+    }
+    for child in &entry.children {
+        collect(child, functions, variables);
+    }
+}
+
+#[test]
+fn test_declaration_attributes() {
+    let source = Source::from_file(
+        "tests/declarations.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned(), "-std=c++17".to_owned()],
+        TUOptionsBuilder::new(),
+        &ParseConfig::new(),
+    )
+    .unwrap();
+    let translation_unit = source
+        .translation_units
+        .into_iter()
+        .next()
+        .unwrap()
+        .unwrap();
+    let mut functions = HashMap::new();
+    let mut variables = HashMap::new();
+    collect(translation_unit.ast(), &mut functions, &mut variables);
+
+    let destructor = &functions[&2];
+    assert!(destructor.is_defaulted);
+    assert!(destructor.is_definition);
+
+    let base_f = &functions[&3];
+    assert!(base_f.is_const);
+    assert!(!base_f.is_override);
+    assert!(!base_f.is_definition);
+    assert_eq!(base_f.exception_specification, ExceptionSpecification::None);
+    assert!(!base_f.exception_specification.is_noexcept());
+
+    let base_g = &functions[&4];
+    assert_eq!(
+        base_g.exception_specification,
+        ExceptionSpecification::BasicNoexcept
+    );
+    assert!(base_g.exception_specification.is_noexcept());
+
+    assert!(functions[&7].is_explicit);
+    assert!(!functions[&8].is_explicit);
+    assert!(functions[&8].is_deleted);
+    assert!(!functions[&8].is_defaulted);
+
+    let derived_f = &functions[&9];
+    assert!(derived_f.is_override);
+    assert!(!derived_f.is_final);
+    assert!(derived_f.is_const);
+
+    let derived_g = &functions[&10];
+    assert!(derived_g.is_final);
+    assert!(!derived_g.is_override);
+    assert!(derived_g.exception_specification.is_noexcept());
+
+    let lvalue_h = &functions[&11];
+    assert_eq!(lvalue_h.ref_qualifier, RefQualifier::LValue);
+    assert!(lvalue_h.is_definition);
+    assert!(lvalue_h.is_inline);
+    assert!(!lvalue_h.is_const);
+    assert_eq!(functions[&12].ref_qualifier, RefQualifier::RValue);
+
+    assert!(functions[&13].is_constexpr);
+    assert!(functions[&13].is_const);
+    assert!(!functions[&11].is_constexpr);
+
+    assert!(functions[&14].is_explicit);
+    assert!(functions[&14].is_const);
+
+    let j = &functions[&16];
+    assert_eq!(j.storage_class, StorageClass::Static);
+    assert_eq!(j.linkage, Linkage::Internal);
+    assert!(j.is_inline);
+    assert!(j.is_definition);
+    assert_eq!(functions[&3].linkage, Linkage::External);
+
+    assert_eq!(
+        variables[&17],
+        VariableAttributes {
+            is_definition: false,
+            storage_class: StorageClass::Extern,
+            linkage: Linkage::External,
+            thread_local: ThreadLocalKind::None,
+            is_constexpr: false,
+            is_const: false,
+        }
+    );
+    assert_eq!(
+        variables[&18],
+        VariableAttributes {
+            is_definition: true,
+            storage_class: StorageClass::None,
+            linkage: Linkage::External,
+            thread_local: ThreadLocalKind::Dynamic,
+            is_constexpr: false,
+            is_const: false,
+        }
+    );
+    assert_eq!(
+        variables[&19],
+        VariableAttributes {
+            is_definition: true,
+            storage_class: StorageClass::None,
+            linkage: Linkage::Internal,
+            thread_local: ThreadLocalKind::None,
+            is_constexpr: true,
+            is_const: true,
+        }
+    );
+    assert_eq!(variables[&20].linkage, Linkage::Internal);
+    assert!(variables[&20].is_definition);
+}
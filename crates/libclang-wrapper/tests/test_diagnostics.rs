@@ -0,0 +1,47 @@
+use libclang_wrapper::source::{Language, Position, Severity, Source};
+
+#[test]
+fn test_diagnostics() {
+    let source = Source::from_string(
+        "struct S {}\nint a;\ndouble a;\nint f() { int unused; return 0; }",
+        Language::Cpp,
+        None,
+        vec!["-Wall".to_owned()],
+    )
+    .unwrap();
+    let translation_unit = source
+        .translation_units
+        .into_iter()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert!(translation_unit.has_errors());
+    let diagnostics = translation_unit.diagnostics();
+    assert_eq!(diagnostics.len(), 3);
+
+    let missing_semicolon = &diagnostics[0];
+    assert_eq!(missing_semicolon.severity, Severity::Error);
+    assert_eq!(missing_semicolon.category, "Parse Issue");
+    assert_eq!(
+        missing_semicolon.position,
+        Position {
+            file_name: "snippet.cpp".to_owned(),
+            line: 1,
+            col: 12
+        }
+    );
+    assert_eq!(missing_semicolon.fix_its.len(), 1);
+    assert_eq!(missing_semicolon.fix_its[0].replacement, ";");
+
+    let redefinition = &diagnostics[1];
+    assert_eq!(redefinition.severity, Severity::Error);
+    assert_eq!(redefinition.children.len(), 1);
+    assert_eq!(redefinition.children[0].severity, Severity::Note);
+    assert_eq!(redefinition.children[0].position.line, 2);
+
+    let unused = &diagnostics[2];
+    assert_eq!(unused.severity, Severity::Warning);
+    assert!(!unused.is_error());
+    assert_eq!(unused.option, "-Wunused-variable");
+    assert_eq!(unused.ranges.len(), 0);
+}
@@ -0,0 +1,928 @@
+use libclang_wrapper::source::{
+    AccessSpecifierType, BinaryOperatorKind, CharacterValue, CodeSpan, CursorKind, CursorType,
+    DeclarationFromPHCMode, DiagnosticsMode, Encoding, Entry, ExceptionSpecification, FloatValue,
+    FunctionAttributes, IntegerValue, Linkage, ParseConfig, Position, RefQualifier, Source,
+    StorageClass, StringValue, TUOptionsBuilder, ThreadLocalKind, VariableAttributes, TU,
+};
+
+fn parse(file_name: &str, args: &[&str]) -> TU {
+    let source = Source::from_file(
+        file_name.to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        args.iter().map(|arg| (*arg).to_owned()).collect(),
+        TUOptionsBuilder::new(),
+        &ParseConfig::new(),
+    )
+    .unwrap();
+    let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
+    translation_units.unwrap().into_iter().next().unwrap()
+}
+
+// span within a single line, `end` is the column after the last character
+fn span(file_name: &str, line: u32, start: u32, end: u32) -> CodeSpan {
+    CodeSpan {
+        start_pos: Position {
+            file_name: file_name.to_owned(),
+            line,
+            col: start,
+        },
+        end_pos: Position {
+            file_name: file_name.to_owned(),
+            line,
+            col: end,
+        },
+    }
+}
+
+fn entry(current_kind: CursorKind, children: Vec<Entry>) -> Entry {
+    Entry {
+        current_kind,
+        children,
+    }
+}
+
+fn leaf(current_kind: CursorKind) -> Entry {
+    entry(current_kind, vec![])
+}
+
+// decimal integer literal without a suffix
+fn integer(spelling: &str) -> IntegerValue {
+    IntegerValue {
+        spelling: spelling.to_owned(),
+        value: spelling.parse().ok(),
+        radix: 10,
+        suffix: "".to_owned(),
+    }
+}
+
+// attributes of a function with external linkage and no specifiers
+fn function_attributes(is_definition: bool) -> FunctionAttributes {
+    FunctionAttributes {
+        is_definition,
+        storage_class: StorageClass::None,
+        linkage: Linkage::External,
+        is_inline: false,
+        is_constexpr: false,
+        is_explicit: false,
+        is_const: false,
+        ref_qualifier: RefQualifier::None,
+        exception_specification: ExceptionSpecification::None,
+        is_defaulted: false,
+        is_deleted: false,
+        is_override: false,
+        is_final: false,
+    }
+}
+
+// attributes of a variable definition without storage class or specifiers
+fn variable_attributes(linkage: Linkage, is_const: bool) -> VariableAttributes {
+    VariableAttributes {
+        is_definition: true,
+        storage_class: StorageClass::None,
+        linkage,
+        thread_local: ThreadLocalKind::None,
+        is_constexpr: false,
+        is_const,
+    }
+}
+
+fn float(spelling: &str, value: f64, suffix: &str) -> FloatValue {
+    FloatValue {
+        spelling: spelling.to_owned(),
+        value: Some(value),
+        suffix: suffix.to_owned(),
+    }
+}
+
+#[test]
+fn test_literals() {
+    let file = "tests/literals.c";
+    assert_eq!(
+        parse(file, &["-x", "c"]).ast(),
+        &entry(
+            CursorKind::Root,
+            vec![
+                entry(
+                    CursorKind::Variable(
+                        "s".to_owned(),
+                        span(file, 1, 1, 22),
+                        CursorType::ConstantArray,
+                        variable_attributes(Linkage::External, true)
+                    ),
+                    vec![leaf(CursorKind::StringLiteral(
+                        "\"hi\"".to_owned(),
+                        span(file, 1, 18, 22),
+                        StringValue {
+                            encoding: Encoding::Ordinary,
+                            raw: false,
+                            contents: "hi".to_owned(),
+                        }
+                    ))]
+                ),
+                entry(
+                    CursorKind::Variable(
+                        "c".to_owned(),
+                        span(file, 2, 1, 12),
+                        CursorType::Int,
+                        variable_attributes(Linkage::External, false)
+                    ),
+                    vec![leaf(CursorKind::CharacterLiteral(
+                        "".to_owned(),
+                        span(file, 2, 9, 12),
+                        CharacterValue {
+                            encoding: Encoding::Ordinary,
+                            code_point: 'a' as u32,
+                        }
+                    ))]
+                ),
+                entry(
+                    CursorKind::Variable(
+                        "z".to_owned(),
+                        span(file, 3, 1, 25),
+                        CursorType::Complex,
+                        variable_attributes(Linkage::External, false)
+                    ),
+                    vec![entry(
+                        CursorKind::ImaginaryLiteral("".to_owned(), span(file, 3, 21, 25)),
+                        vec![leaf(CursorKind::FloatLiteral(
+                            span(file, 3, 21, 25),
+                            float("1.0i", 1.0, "i")
+                        ))]
+                    )]
+                ),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_members() {
+    let file = "tests/members.c";
+    let point = |line, start| {
+        leaf(CursorKind::TypeReference(
+            "struct point".to_owned(),
+            span(file, line, start, start + 5),
+        ))
+    };
+    assert_eq!(
+        parse(file, &["-x", "c"]).ast(),
+        &entry(
+            CursorKind::Root,
+            vec![
+                entry(
+                    CursorKind::Struct(
+                        "point".to_owned(),
+                        span(file, 1, 1, 24),
+                        AccessSpecifierType::Invalid
+                    ),
+                    vec![leaf(CursorKind::Field(
+                        "x".to_owned(),
+                        span(file, 1, 16, 21),
+                        AccessSpecifierType::Invalid,
+                        CursorType::Int
+                    ))]
+                ),
+                entry(
+                    CursorKind::Function {
+                        spelling: "f".to_owned(),
+                        display_name: "f(struct point)".to_owned(),
+                        code_span: span(file, 2, 1, 36),
+                        cur_type: CursorType::FunctionProto,
+                        return_type: CursorType::Void,
+                        canonical_return_type: CursorType::Void,
+                        attributes: function_attributes(true)
+                    },
+                    vec![
+                        entry(
+                            CursorKind::Parameter(
+                                "p".to_owned(),
+                                span(file, 2, 8, 22),
+                                CursorType::Elaborated
+                            ),
+                            vec![point(2, 15)]
+                        ),
+                        entry(
+                            CursorKind::CompoundStatement(span(file, 2, 24, 36)),
+                            vec![entry(
+                                CursorKind::BinaryOperator(
+                                    span(file, 2, 26, 33),
+                                    BinaryOperatorKind::Assign
+                                ),
+                                vec![
+                                    entry(
+                                        CursorKind::MemberReferenceExpression(
+                                            "x".to_owned(),
+                                            span(file, 2, 26, 29)
+                                        ),
+                                        vec![leaf(CursorKind::DeclarationReferenceExpression(
+                                            "p".to_owned(),
+                                            span(file, 2, 26, 27)
+                                        ))]
+                                    ),
+                                    leaf(CursorKind::IntegerLiteral(
+                                        span(file, 2, 32, 33),
+                                        integer("1")
+                                    )),
+                                ]
+                            )]
+                        ),
+                    ]
+                ),
+                entry(
+                    CursorKind::Variable(
+                        "q".to_owned(),
+                        span(file, 3, 1, 28),
+                        CursorType::Elaborated,
+                        variable_attributes(Linkage::External, false)
+                    ),
+                    vec![
+                        point(3, 8),
+                        entry(
+                            CursorKind::InitializerListExpression(
+                                "".to_owned(),
+                                span(file, 3, 18, 28)
+                            ),
+                            // the designated initializer
+                            vec![entry(
+                                CursorKind::UnexposedExpression(
+                                    "".to_owned(),
+                                    span(file, 3, 20, 26)
+                                ),
+                                vec![
+                                    leaf(CursorKind::MemberReference(
+                                        "x".to_owned(),
+                                        span(file, 3, 21, 22)
+                                    )),
+                                    leaf(CursorKind::IntegerLiteral(
+                                        span(file, 3, 25, 26),
+                                        integer("2")
+                                    )),
+                                ]
+                            )]
+                        ),
+                    ]
+                ),
+                entry(
+                    CursorKind::Function {
+                        spelling: "r".to_owned(),
+                        display_name: "r()".to_owned(),
+                        code_span: span(file, 4, 1, 53),
+                        cur_type: CursorType::FunctionProto,
+                        return_type: CursorType::Elaborated,
+                        canonical_return_type: CursorType::Record,
+                        attributes: function_attributes(true)
+                    },
+                    vec![
+                        point(4, 8),
+                        entry(
+                            CursorKind::CompoundStatement(span(file, 4, 22, 53)),
+                            vec![entry(
+                                CursorKind::ReturnStatement(span(file, 4, 24, 50)),
+                                vec![entry(
+                                    CursorKind::UnexposedExpression(
+                                        "".to_owned(),
+                                        span(file, 4, 31, 50)
+                                    ),
+                                    vec![entry(
+                                        CursorKind::CompoundLiteralExpression(
+                                            "".to_owned(),
+                                            span(file, 4, 31, 50)
+                                        ),
+                                        vec![
+                                            point(4, 39),
+                                            entry(
+                                                CursorKind::InitializerListExpression(
+                                                    "".to_owned(),
+                                                    span(file, 4, 45, 50)
+                                                ),
+                                                vec![leaf(CursorKind::IntegerLiteral(
+                                                    span(file, 4, 47, 48),
+                                                    integer("3")
+                                                ))]
+                                            ),
+                                        ]
+                                    )]
+                                )]
+                            )]
+                        ),
+                    ]
+                ),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_operators_and_casts() {
+    let file = "tests/operators.c";
+    let parameter = |name: &str, line, start, end| {
+        entry(
+            CursorKind::UnexposedExpression(name.to_owned(), span(file, line, start, end)),
+            vec![leaf(CursorKind::DeclarationReferenceExpression(
+                name.to_owned(),
+                span(file, line, start, end),
+            ))],
+        )
+    };
+    assert_eq!(
+        parse(file, &["-x", "c"]).ast(),
+        &entry(
+            CursorKind::Root,
+            vec![entry(
+                CursorKind::Function {
+                    spelling: "f".to_owned(),
+                    display_name: "f(int *, int)".to_owned(),
+                    code_span: CodeSpan {
+                        start_pos: Position {
+                            file_name: file.to_owned(),
+                            line: 1,
+                            col: 1
+                        },
+                        end_pos: Position {
+                            file_name: file.to_owned(),
+                            line: 4,
+                            col: 2
+                        }
+                    },
+                    cur_type: CursorType::FunctionProto,
+                    return_type: CursorType::Int,
+                    canonical_return_type: CursorType::Int,
+                    attributes: function_attributes(true)
+                },
+                vec![
+                    leaf(CursorKind::Parameter(
+                        "p".to_owned(),
+                        span(file, 1, 7, 13),
+                        CursorType::Pointer
+                    )),
+                    leaf(CursorKind::Parameter(
+                        "a".to_owned(),
+                        span(file, 1, 15, 20),
+                        CursorType::Int
+                    )),
+                    entry(
+                        CursorKind::CompoundStatement(CodeSpan {
+                            start_pos: Position {
+                                file_name: file.to_owned(),
+                                line: 1,
+                                col: 22
+                            },
+                            end_pos: Position {
+                                file_name: file.to_owned(),
+                                line: 4,
+                                col: 2
+                            }
+                        }),
+                        vec![
+                            entry(
+                                CursorKind::CompoundAssignOperator(
+                                    "".to_owned(),
+                                    span(file, 2, 3, 20),
+                                    BinaryOperatorKind::AddAssign
+                                ),
+                                vec![
+                                    entry(
+                                        CursorKind::ArraySubscription(
+                                            "".to_owned(),
+                                            span(file, 2, 3, 7)
+                                        ),
+                                        vec![
+                                            parameter("p", 2, 3, 4),
+                                            leaf(CursorKind::IntegerLiteral(
+                                                span(file, 2, 5, 6),
+                                                integer("0")
+                                            )),
+                                        ]
+                                    ),
+                                    entry(
+                                        CursorKind::ConditionalOperator(
+                                            "".to_owned(),
+                                            span(file, 2, 11, 20)
+                                        ),
+                                        vec![
+                                            parameter("a", 2, 11, 12),
+                                            leaf(CursorKind::IntegerLiteral(
+                                                span(file, 2, 15, 16),
+                                                integer("1")
+                                            )),
+                                            leaf(CursorKind::IntegerLiteral(
+                                                span(file, 2, 19, 20),
+                                                integer("2")
+                                            )),
+                                        ]
+                                    ),
+                                ]
+                            ),
+                            entry(
+                                CursorKind::ReturnStatement(span(file, 3, 3, 18)),
+                                vec![entry(
+                                    CursorKind::CStyleCast("".to_owned(), span(file, 3, 10, 18)),
+                                    vec![leaf(CursorKind::FloatLiteral(
+                                        span(file, 3, 15, 18),
+                                        float("2.5", 2.5, "")
+                                    ))]
+                                )]
+                            ),
+                        ]
+                    ),
+                ]
+            )]
+        )
+    );
+}
+
+#[test]
+fn test_labels_and_blocks() {
+    let file = "tests/labels_and_blocks.c";
+    assert_eq!(
+        parse(file, &["-x", "c", "-fblocks"]).ast(),
+        &entry(
+            CursorKind::Root,
+            vec![
+                entry(
+                    CursorKind::Function {
+                        spelling: "g".to_owned(),
+                        display_name: "g()".to_owned(),
+                        code_span: span(file, 1, 1, 35),
+                        cur_type: CursorType::FunctionProto,
+                        return_type: CursorType::Void,
+                        canonical_return_type: CursorType::Void,
+                        attributes: function_attributes(true)
+                    },
+                    vec![entry(
+                        CursorKind::CompoundStatement(span(file, 1, 14, 35)),
+                        vec![
+                            entry(
+                                CursorKind::GotoStatement(span(file, 1, 16, 25)),
+                                vec![leaf(CursorKind::LabelReference(
+                                    "done".to_owned(),
+                                    span(file, 1, 21, 25)
+                                ))]
+                            ),
+                            entry(
+                                CursorKind::LabelStatement(
+                                    "done".to_owned(),
+                                    span(file, 1, 27, 33)
+                                ),
+                                vec![leaf(CursorKind::NullStatement(span(file, 1, 32, 33)))]
+                            ),
+                        ]
+                    )]
+                ),
+                entry(
+                    CursorKind::Variable(
+                        "b".to_owned(),
+                        span(file, 2, 1, 23),
+                        CursorType::BlockPointer,
+                        variable_attributes(Linkage::External, false)
+                    ),
+                    vec![entry(
+                        CursorKind::BlockExpression("".to_owned(), span(file, 2, 19, 23)),
+                        // the block declaration
+                        vec![entry(
+                            CursorKind::Unexposed("".to_owned()),
+                            vec![leaf(CursorKind::CompoundStatement(span(file, 2, 20, 23)))]
+                        )]
+                    )]
+                ),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_references() {
+    let file = "tests/references.cpp";
+    let function = |parameter_type, start, end, parameter: (u32, u32)| {
+        entry(
+            CursorKind::Function {
+                spelling: "h".to_owned(),
+                display_name: format!("h({})", parameter_type),
+                code_span: span(file, 1, start, end),
+                cur_type: CursorType::FunctionProto,
+                return_type: CursorType::Void,
+                canonical_return_type: CursorType::Void,
+                attributes: function_attributes(false),
+            },
+            vec![leaf(CursorKind::Parameter(
+                "".to_owned(),
+                span(file, 1, parameter.0, parameter.1),
+                if parameter_type == "int" {
+                    CursorType::Int
+                } else {
+                    CursorType::Double
+                },
+            ))],
+        )
+    };
+    assert_eq!(
+        parse(file, &["-x", "c++"]).ast(),
+        &entry(
+            CursorKind::Root,
+            vec![
+                entry(
+                    CursorKind::Namespace("n".to_owned(), span(file, 1, 1, 45)),
+                    vec![
+                        function("int", 15, 26, (22, 25)),
+                        function("double", 28, 42, (35, 41)),
+                    ]
+                ),
+                entry(
+                    CursorKind::NotSupported(
+                        "h".to_owned(),
+                        span(file, 2, 1, 11),
+                        clang_sys::CXCursor_UsingDeclaration
+                    ),
+                    vec![
+                        leaf(CursorKind::NamespaceReference(
+                            "n".to_owned(),
+                            span(file, 2, 7, 8)
+                        )),
+                        leaf(CursorKind::OverloadedDeclarationReference(
+                            "h".to_owned(),
+                            span(file, 2, 10, 11)
+                        )),
+                    ]
+                ),
+                entry(
+                    CursorKind::Function {
+                        spelling: "main".to_owned(),
+                        display_name: "main()".to_owned(),
+                        code_span: span(file, 3, 1, 45),
+                        cur_type: CursorType::FunctionProto,
+                        return_type: CursorType::Int,
+                        canonical_return_type: CursorType::Int,
+                        attributes: function_attributes(true)
+                    },
+                    vec![entry(
+                        CursorKind::CompoundStatement(span(file, 3, 12, 45)),
+                        vec![
+                            entry(
+                                CursorKind::DeclarationStatement(span(file, 3, 14, 24)),
+                                vec![entry(
+                                    CursorKind::Variable(
+                                        "a".to_owned(),
+                                        span(file, 3, 14, 23),
+                                        CursorType::Int,
+                                        variable_attributes(Linkage::NoLinkage, false)
+                                    ),
+                                    vec![leaf(CursorKind::IntegerLiteral(
+                                        span(file, 3, 22, 23),
+                                        integer("0")
+                                    ))]
+                                )]
+                            ),
+                            entry(
+                                CursorKind::LambdaExpression(span(file, 3, 25, 42)),
+                                vec![
+                                    leaf(CursorKind::VariableReference(
+                                        "a".to_owned(),
+                                        span(file, 3, 26, 27)
+                                    )),
+                                    entry(
+                                        CursorKind::CompoundStatement(span(file, 3, 29, 42)),
+                                        vec![entry(
+                                            CursorKind::ReturnStatement(span(file, 3, 31, 39)),
+                                            vec![entry(
+                                                CursorKind::UnexposedExpression(
+                                                    "a".to_owned(),
+                                                    span(file, 3, 38, 39)
+                                                ),
+                                                vec![leaf(
+                                                    CursorKind::DeclarationReferenceExpression(
+                                                        "a".to_owned(),
+                                                        span(file, 3, 38, 39)
+                                                    )
+                                                )]
+                                            )]
+                                        )]
+                                    ),
+                                ]
+                            ),
+                        ]
+                    )]
+                ),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_cxx_expressions() {
+    let file = "tests/cxx_expressions.cpp";
+    let p = |line, start| {
+        leaf(CursorKind::DeclarationReferenceExpression(
+            "p".to_owned(),
+            span(file, line, start, start + 1),
+        ))
+    };
+    let p_value = |line, start| {
+        entry(
+            CursorKind::UnexposedExpression("p".to_owned(), span(file, line, start, start + 1)),
+            vec![p(line, start)],
+        )
+    };
+    let to_void = |line, children| {
+        entry(
+            CursorKind::CStyleCast("".to_owned(), span(file, line, 3, 20)),
+            children,
+        )
+    };
+    assert_eq!(
+        parse(file, &["-x", "c++", "-std=c++17"]).ast(),
+        &entry(
+            CursorKind::Root,
+            vec![entry(
+                CursorKind::Function {
+                    spelling: "f".to_owned(),
+                    display_name: "f(int *)".to_owned(),
+                    code_span: CodeSpan {
+                        start_pos: Position {
+                            file_name: file.to_owned(),
+                            line: 1,
+                            col: 1
+                        },
+                        end_pos: Position {
+                            file_name: file.to_owned(),
+                            line: 11,
+                            col: 2
+                        }
+                    },
+                    cur_type: CursorType::FunctionProto,
+                    return_type: CursorType::Void,
+                    canonical_return_type: CursorType::Void,
+                    attributes: function_attributes(true)
+                },
+                vec![
+                    leaf(CursorKind::Parameter(
+                        "p".to_owned(),
+                        span(file, 1, 8, 14),
+                        CursorType::Pointer
+                    )),
+                    entry(
+                        CursorKind::CompoundStatement(CodeSpan {
+                            start_pos: Position {
+                                file_name: file.to_owned(),
+                                line: 1,
+                                col: 16
+                            },
+                            end_pos: Position {
+                                file_name: file.to_owned(),
+                                line: 11,
+                                col: 2
+                            }
+                        }),
+                        vec![
+                            entry(
+                                CursorKind::BinaryOperator(
+                                    span(file, 2, 3, 14),
+                                    BinaryOperatorKind::Assign
+                                ),
+                                vec![
+                                    p(2, 3),
+                                    entry(
+                                        CursorKind::UnexposedExpression(
+                                            "".to_owned(),
+                                            span(file, 2, 7, 14)
+                                        ),
+                                        vec![leaf(CursorKind::NullptrLiteral(span(
+                                            file, 2, 7, 14
+                                        )))]
+                                    ),
+                                ]
+                            ),
+                            entry(
+                                CursorKind::BinaryOperator(
+                                    span(file, 3, 3, 14),
+                                    BinaryOperatorKind::Assign
+                                ),
+                                vec![
+                                    p(3, 3),
+                                    leaf(CursorKind::NewExpression(span(file, 3, 7, 14)))
+                                ]
+                            ),
+                            entry(
+                                CursorKind::DeleteExpression(span(file, 4, 3, 11)),
+                                vec![p_value(4, 10)]
+                            ),
+                            entry(
+                                CursorKind::BinaryOperator(
+                                    span(file, 5, 3, 28),
+                                    BinaryOperatorKind::Assign
+                                ),
+                                vec![
+                                    p(5, 3),
+                                    entry(
+                                        CursorKind::StaticCast(
+                                            span(file, 5, 7, 28),
+                                            CursorType::Pointer
+                                        ),
+                                        vec![p_value(5, 26)]
+                                    ),
+                                ]
+                            ),
+                            to_void(6, vec![leaf(CursorKind::SizeOf(span(file, 6, 9, 20)))]),
+                            to_void(
+                                7,
+                                vec![entry(
+                                    CursorKind::NoexceptExpression(span(file, 7, 9, 20)),
+                                    vec![p(7, 15)]
+                                )]
+                            ),
+                            entry(
+                                CursorKind::LambdaExpression(span(file, 8, 3, 8)),
+                                vec![leaf(CursorKind::CompoundStatement(span(file, 8, 6, 8)))]
+                            ),
+                            entry(
+                                CursorKind::ThrowExpression(span(file, 9, 3, 10)),
+                                vec![leaf(CursorKind::IntegerLiteral(
+                                    span(file, 9, 9, 10),
+                                    integer("1")
+                                ))]
+                            ),
+                            entry(
+                                CursorKind::TryStatement(span(file, 10, 3, 24)),
+                                vec![
+                                    leaf(CursorKind::CompoundStatement(span(file, 10, 7, 9))),
+                                    entry(
+                                        CursorKind::CatchStatement(span(file, 10, 10, 24)),
+                                        vec![leaf(CursorKind::CompoundStatement(span(
+                                            file, 10, 22, 24
+                                        )))]
+                                    ),
+                                ]
+                            ),
+                        ]
+                    ),
+                ]
+            )]
+        )
+    );
+}
+
+#[test]
+fn test_literal_values() {
+    let file = "tests/literal_values.cpp";
+    let variable = |name: &str, line, end, cursor_type, literal| {
+        // `const` variables have internal linkage in C++
+        let attributes = match name {
+            "e" | "f" => variable_attributes(Linkage::Internal, true),
+            _ => variable_attributes(Linkage::External, false),
+        };
+        entry(
+            CursorKind::Variable(
+                name.to_owned(),
+                span(file, line, 1, end),
+                cursor_type,
+                attributes,
+            ),
+            vec![leaf(literal)],
+        )
+    };
+    assert_eq!(
+        parse(file, &["-x", "c++", "-std=c++14"]).ast(),
+        &entry(
+            CursorKind::Root,
+            vec![
+                variable(
+                    "a",
+                    1,
+                    31,
+                    CursorType::UnsignedLongLong,
+                    CursorKind::IntegerLiteral(
+                        span(file, 1, 24, 31),
+                        IntegerValue {
+                            spelling: "0x1Full".to_owned(),
+                            value: Some(31),
+                            radix: 16,
+                            suffix: "ull".to_owned(),
+                        }
+                    )
+                ),
+                variable(
+                    "b",
+                    2,
+                    14,
+                    CursorType::Int,
+                    CursorKind::IntegerLiteral(
+                        span(file, 2, 9, 14),
+                        IntegerValue {
+                            spelling: "1'000".to_owned(),
+                            value: Some(1000),
+                            radix: 10,
+                            suffix: "".to_owned(),
+                        }
+                    )
+                ),
+                variable(
+                    "c",
+                    3,
+                    17,
+                    CursorType::Float,
+                    CursorKind::FloatLiteral(span(file, 3, 11, 17), float("1.5e3f", 1500.0, "f"))
+                ),
+                variable(
+                    "d",
+                    4,
+                    14,
+                    CursorType::Bool,
+                    CursorKind::BoolLiteral(span(file, 4, 10, 14), true)
+                ),
+                variable(
+                    "e",
+                    5,
+                    26,
+                    CursorType::ConstantArray,
+                    CursorKind::StringLiteral(
+                        "u8\"hi\\n\"".to_owned(),
+                        span(file, 5, 18, 26),
+                        StringValue {
+                            encoding: Encoding::Utf8,
+                            raw: false,
+                            contents: "hi\n".to_owned(),
+                        }
+                    )
+                ),
+                variable(
+                    "f",
+                    6,
+                    30,
+                    CursorType::ConstantArray,
+                    CursorKind::StringLiteral(
+                        "L\"a\\\\b\"".to_owned(),
+                        span(file, 6, 21, 30),
+                        StringValue {
+                            encoding: Encoding::Wide,
+                            raw: true,
+                            contents: "a\\b".to_owned(),
+                        }
+                    )
+                ),
+                variable(
+                    "g",
+                    7,
+                    21,
+                    CursorType::Char32,
+                    CursorKind::CharacterLiteral(
+                        "".to_owned(),
+                        span(file, 7, 14, 21),
+                        CharacterValue {
+                            encoding: Encoding::Utf32,
+                            code_point: 0x41,
+                        }
+                    )
+                ),
+            ]
+        )
+    );
+}
+
+// values of the string and character literals in the tree, in order
+fn literal_values(
+    entry: &Entry,
+    strings: &mut Vec<StringValue>,
+    characters: &mut Vec<CharacterValue>,
+) {
+    match &entry.current_kind {
+        CursorKind::StringLiteral(_, _, value) => strings.push(value.clone()),
+        CursorKind::CharacterLiteral(_, _, value) => characters.push(value.clone()),
+        _ => {}
+    }
+    for child in &entry.children {
+        literal_values(child, strings, characters);
+    }
+}
+
+#[test]
+fn test_literal_macros() {
+    let mut strings = vec![];
+    let mut characters = vec![];
+    literal_values(
+        parse("tests/literal_macros.cpp", &["-x", "c++", "-std=c++14"]).ast(),
+        &mut strings,
+        &mut characters,
+    );
+    // the tokens are the macro names, which must not be taken for the literals
+    assert_eq!(
+        strings,
+        vec![StringValue {
+            encoding: Encoding::Ordinary,
+            raw: false,
+            contents: "hunter2".to_owned(),
+        }]
+    );
+    assert_eq!(
+        characters,
+        vec![
+            CharacterValue {
+                encoding: Encoding::Utf32,
+                code_point: 'x' as u32,
+            },
+            CharacterValue {
+                encoding: Encoding::Ordinary,
+                code_point: '\n' as u32,
+            },
+        ]
+    );
+}
@@ -1,7 +1,8 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
-    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry, Position,
-    Source, TUOptionsBuilder,
+    BinaryOperatorKind, CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode,
+    Entry, ExceptionSpecification, FunctionAttributes, IntegerValue, Linkage, ParseConfig,
+    Position, RefQualifier, Source, StorageClass, TUOptionsBuilder, ThreadLocalKind,
+    UnaryOperatorKind, VariableAttributes,
 };
 
 #[test]
@@ -12,10 +13,11 @@ fn test_classic_for_loop() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -39,7 +41,22 @@ fn test_classic_for_loop() {
                     },
                     cur_type: CursorType::FunctionProto,
                     return_type: CursorType::Void,
-                    canonical_return_type: CursorType::Void
+                    canonical_return_type: CursorType::Void,
+                    attributes: FunctionAttributes {
+                        is_definition: true,
+                        storage_class: StorageClass::None,
+                        linkage: Linkage::External,
+                        is_inline: false,
+                        is_constexpr: false,
+                        is_explicit: false,
+                        is_const: false,
+                        ref_qualifier: RefQualifier::None,
+                        exception_specification: ExceptionSpecification::None,
+                        is_defaulted: false,
+                        is_deleted: false,
+                        is_override: false,
+                        is_final: false,
+                    }
                 },
                 children: vec![Entry {
                     current_kind: CursorKind::CompoundStatement(CodeSpan {
@@ -96,38 +113,57 @@ fn test_classic_for_loop() {
                                                 col: 17
                                             }
                                         },
-                                        CursorType::Int
+                                        CursorType::Int,
+                                        VariableAttributes {
+                                            is_definition: true,
+                                            storage_class: StorageClass::None,
+                                            linkage: Linkage::NoLinkage,
+                                            thread_local: ThreadLocalKind::None,
+                                            is_constexpr: false,
+                                            is_const: false,
+                                        }
                                     ),
                                     children: vec![Entry {
-                                        current_kind: CursorKind::IntegerLiteral(CodeSpan {
-                                            start_pos: Position {
-                                                file_name: "tests/for.cpp".to_owned(),
-                                                line: 2,
-                                                col: 16
+                                        current_kind: CursorKind::IntegerLiteral(
+                                            CodeSpan {
+                                                start_pos: Position {
+                                                    file_name: "tests/for.cpp".to_owned(),
+                                                    line: 2,
+                                                    col: 16
+                                                },
+                                                end_pos: Position {
+                                                    file_name: "tests/for.cpp".to_owned(),
+                                                    line: 2,
+                                                    col: 17
+                                                }
                                             },
-                                            end_pos: Position {
-                                                file_name: "tests/for.cpp".to_owned(),
-                                                line: 2,
-                                                col: 17
+                                            IntegerValue {
+                                                spelling: "0".to_owned(),
+                                                value: Some(0),
+                                                radix: 10,
+                                                suffix: "".to_owned()
                                             }
-                                        }),
+                                        ),
                                         children: vec![]
                                     }]
                                 }]
                             },
                             Entry {
-                                current_kind: CursorKind::BinaryOperator(CodeSpan {
-                                    start_pos: Position {
-                                        file_name: "tests/for.cpp".to_owned(),
-                                        line: 2,
-                                        col: 19
+                                current_kind: CursorKind::BinaryOperator(
+                                    CodeSpan {
+                                        start_pos: Position {
+                                            file_name: "tests/for.cpp".to_owned(),
+                                            line: 2,
+                                            col: 19
+                                        },
+                                        end_pos: Position {
+                                            file_name: "tests/for.cpp".to_owned(),
+                                            line: 2,
+                                            col: 24
+                                        }
                                     },
-                                    end_pos: Position {
-                                        file_name: "tests/for.cpp".to_owned(),
-                                        line: 2,
-                                        col: 24
-                                    }
-                                }),
+                                    BinaryOperatorKind::Less
+                                ),
                                 children: vec![
                                     Entry {
                                         current_kind: CursorKind::UnexposedExpression(
@@ -166,35 +202,46 @@ fn test_classic_for_loop() {
                                         }]
                                     },
                                     Entry {
-                                        current_kind: CursorKind::IntegerLiteral(CodeSpan {
-                                            start_pos: Position {
-                                                file_name: "tests/for.cpp".to_owned(),
-                                                line: 2,
-                                                col: 23
+                                        current_kind: CursorKind::IntegerLiteral(
+                                            CodeSpan {
+                                                start_pos: Position {
+                                                    file_name: "tests/for.cpp".to_owned(),
+                                                    line: 2,
+                                                    col: 23
+                                                },
+                                                end_pos: Position {
+                                                    file_name: "tests/for.cpp".to_owned(),
+                                                    line: 2,
+                                                    col: 24
+                                                }
                                             },
-                                            end_pos: Position {
-                                                file_name: "tests/for.cpp".to_owned(),
-                                                line: 2,
-                                                col: 24
+                                            IntegerValue {
+                                                spelling: "5".to_owned(),
+                                                value: Some(5),
+                                                radix: 10,
+                                                suffix: "".to_owned()
                                             }
-                                        }),
+                                        ),
                                         children: vec![]
                                     }
                                 ]
                             },
                             Entry {
-                                current_kind: CursorKind::UnaryOperator(CodeSpan {
-                                    start_pos: Position {
-                                        file_name: "tests/for.cpp".to_owned(),
-                                        line: 2,
-                                        col: 26
+                                current_kind: CursorKind::UnaryOperator(
+                                    CodeSpan {
+                                        start_pos: Position {
+                                            file_name: "tests/for.cpp".to_owned(),
+                                            line: 2,
+                                            col: 26
+                                        },
+                                        end_pos: Position {
+                                            file_name: "tests/for.cpp".to_owned(),
+                                            line: 2,
+                                            col: 29
+                                        }
                                     },
-                                    end_pos: Position {
-                                        file_name: "tests/for.cpp".to_owned(),
-                                        line: 2,
-                                        col: 29
-                                    }
-                                }),
+                                    UnaryOperatorKind::PreIncrement
+                                ),
                                 children: vec![Entry {
                                     current_kind: CursorKind::DeclarationReferenceExpression(
                                         "i".to_owned(),
@@ -259,10 +306,11 @@ fn test_for_no_initializer() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -286,7 +334,22 @@ fn test_for_no_initializer() {
                     },
                     cur_type: CursorType::FunctionProto,
                     return_type: CursorType::Void,
-                    canonical_return_type: CursorType::Void
+                    canonical_return_type: CursorType::Void,
+                    attributes: FunctionAttributes {
+                        is_definition: true,
+                        storage_class: StorageClass::None,
+                        linkage: Linkage::External,
+                        is_inline: false,
+                        is_constexpr: false,
+                        is_explicit: false,
+                        is_const: false,
+                        ref_qualifier: RefQualifier::None,
+                        exception_specification: ExceptionSpecification::None,
+                        is_defaulted: false,
+                        is_deleted: false,
+                        is_override: false,
+                        is_final: false,
+                    }
                 },
                 children: vec![Entry {
                     current_kind: CursorKind::CompoundStatement(CodeSpan {
@@ -330,21 +393,37 @@ fn test_for_no_initializer() {
                                             col: 12
                                         }
                                     },
-                                    CursorType::Int
+                                    CursorType::Int,
+                                    VariableAttributes {
+                                        is_definition: true,
+                                        storage_class: StorageClass::None,
+                                        linkage: Linkage::NoLinkage,
+                                        thread_local: ThreadLocalKind::None,
+                                        is_constexpr: false,
+                                        is_const: false,
+                                    }
                                 ),
                                 children: vec![Entry {
-                                    current_kind: CursorKind::IntegerLiteral(CodeSpan {
-                                        start_pos: Position {
-                                            file_name: "tests/for_no_init.cpp".to_owned(),
-                                            line: 2,
-                                            col: 11
+                                    current_kind: CursorKind::IntegerLiteral(
+                                        CodeSpan {
+                                            start_pos: Position {
+                                                file_name: "tests/for_no_init.cpp".to_owned(),
+                                                line: 2,
+                                                col: 11
+                                            },
+                                            end_pos: Position {
+                                                file_name: "tests/for_no_init.cpp".to_owned(),
+                                                line: 2,
+                                                col: 12
+                                            }
                                         },
-                                        end_pos: Position {
-                                            file_name: "tests/for_no_init.cpp".to_owned(),
-                                            line: 2,
-                                            col: 12
+                                        IntegerValue {
+                                            spelling: "0".to_owned(),
+                                            value: Some(0),
+                                            radix: 10,
+                                            suffix: "".to_owned()
                                         }
-                                    }),
+                                    ),
                                     children: vec![]
                                 }]
                             }]
@@ -364,18 +443,21 @@ fn test_for_no_initializer() {
                             }),
                             children: vec![
                                 Entry {
-                                    current_kind: CursorKind::BinaryOperator(CodeSpan {
-                                        start_pos: Position {
-                                            file_name: "tests/for_no_init.cpp".to_owned(),
-                                            line: 3,
-                                            col: 10
+                                    current_kind: CursorKind::BinaryOperator(
+                                        CodeSpan {
+                                            start_pos: Position {
+                                                file_name: "tests/for_no_init.cpp".to_owned(),
+                                                line: 3,
+                                                col: 10
+                                            },
+                                            end_pos: Position {
+                                                file_name: "tests/for_no_init.cpp".to_owned(),
+                                                line: 3,
+                                                col: 15
+                                            }
                                         },
-                                        end_pos: Position {
-                                            file_name: "tests/for_no_init.cpp".to_owned(),
-                                            line: 3,
-                                            col: 15
-                                        }
-                                    }),
+                                        BinaryOperatorKind::Less
+                                    ),
                                     children: vec![
                                         Entry {
                                             current_kind: CursorKind::UnexposedExpression(
@@ -418,35 +500,48 @@ fn test_for_no_initializer() {
                                             }]
                                         },
                                         Entry {
-                                            current_kind: CursorKind::IntegerLiteral(CodeSpan {
-                                                start_pos: Position {
-                                                    file_name: "tests/for_no_init.cpp".to_owned(),
-                                                    line: 3,
-                                                    col: 14
+                                            current_kind: CursorKind::IntegerLiteral(
+                                                CodeSpan {
+                                                    start_pos: Position {
+                                                        file_name: "tests/for_no_init.cpp"
+                                                            .to_owned(),
+                                                        line: 3,
+                                                        col: 14
+                                                    },
+                                                    end_pos: Position {
+                                                        file_name: "tests/for_no_init.cpp"
+                                                            .to_owned(),
+                                                        line: 3,
+                                                        col: 15
+                                                    }
                                                 },
-                                                end_pos: Position {
-                                                    file_name: "tests/for_no_init.cpp".to_owned(),
-                                                    line: 3,
-                                                    col: 15
+                                                IntegerValue {
+                                                    spelling: "5".to_owned(),
+                                                    value: Some(5),
+                                                    radix: 10,
+                                                    suffix: "".to_owned()
                                                 }
-                                            }),
+                                            ),
                                             children: vec![]
                                         }
                                     ]
                                 },
                                 Entry {
-                                    current_kind: CursorKind::UnaryOperator(CodeSpan {
-                                        start_pos: Position {
-                                            file_name: "tests/for_no_init.cpp".to_owned(),
-                                            line: 3,
-                                            col: 17
+                                    current_kind: CursorKind::UnaryOperator(
+                                        CodeSpan {
+                                            start_pos: Position {
+                                                file_name: "tests/for_no_init.cpp".to_owned(),
+                                                line: 3,
+                                                col: 17
+                                            },
+                                            end_pos: Position {
+                                                file_name: "tests/for_no_init.cpp".to_owned(),
+                                                line: 3,
+                                                col: 20
+                                            }
                                         },
-                                        end_pos: Position {
-                                            file_name: "tests/for_no_init.cpp".to_owned(),
-                                            line: 3,
-                                            col: 20
-                                        }
-                                    }),
+                                        UnaryOperatorKind::PreIncrement
+                                    ),
                                     children: vec![Entry {
                                         current_kind: CursorKind::DeclarationReferenceExpression(
                                             "i".to_owned(),
@@ -498,10 +593,11 @@ fn test_for_range_based() {
         DiagnosticsMode::Enabled,
         vec!["-std=c+14".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -525,7 +621,22 @@ fn test_for_range_based() {
                     },
                     cur_type: CursorType::FunctionProto,
                     return_type: CursorType::Void,
-                    canonical_return_type: CursorType::Void
+                    canonical_return_type: CursorType::Void,
+                    attributes: FunctionAttributes {
+                        is_definition: true,
+                        storage_class: StorageClass::None,
+                        linkage: Linkage::External,
+                        is_inline: false,
+                        is_constexpr: false,
+                        is_explicit: false,
+                        is_const: false,
+                        ref_qualifier: RefQualifier::None,
+                        exception_specification: ExceptionSpecification::None,
+                        is_defaulted: false,
+                        is_deleted: false,
+                        is_override: false,
+                        is_final: false,
+                    }
                 },
                 children: vec![
                     Entry {
@@ -626,7 +737,15 @@ fn test_for_range_based() {
                                                 col: 25
                                             }
                                         },
-                                        CursorType::LValueReference
+                                        CursorType::LValueReference,
+                                        VariableAttributes {
+                                            is_definition: true,
+                                            storage_class: StorageClass::None,
+                                            linkage: Linkage::NoLinkage,
+                                            thread_local: ThreadLocalKind::None,
+                                            is_constexpr: false,
+                                            is_const: false,
+                                        }
                                     ),
                                     children: vec![Entry {
                                         current_kind: CursorKind::CallExpression(
@@ -0,0 +1,43 @@
+use libclang_wrapper::source::{CursorKind, Language, Source};
+
+#[test]
+fn test_parsing_from_string() {
+    let source = Source::from_string(
+        "namespace ns { constexpr auto answer = 42; }",
+        Language::Cpp,
+        Some("c++17"),
+        vec!["-DUNUSED".to_owned()],
+    )
+    .unwrap();
+    let translation_unit = source
+        .translation_units
+        .into_iter()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(translation_unit.file_name(), "snippet.cpp");
+    assert_eq!(
+        translation_unit.info().arguments,
+        vec!["-x", "c++", "-std=c++17", "-DUNUSED"]
+    );
+    let ast = translation_unit.ast();
+    assert_eq!(ast.current_kind, CursorKind::Root);
+    assert_eq!(ast.children.len(), 1);
+    assert!(matches!(
+        &ast.children[0].current_kind,
+        CursorKind::Namespace(name, _) if name == "ns"
+    ));
+}
+
+#[test]
+fn test_parsing_c_from_string() {
+    let source = Source::from_string("int f(void);", Language::C, None, vec![]).unwrap();
+    let translation_unit = source
+        .translation_units
+        .into_iter()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(translation_unit.file_name(), "snippet.c");
+    assert_eq!(translation_unit.ast().children.len(), 1);
+}
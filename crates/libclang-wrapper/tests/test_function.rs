@@ -1,7 +1,7 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
-    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry, Position,
-    Source, TUOptionsBuilder,
+    BinaryOperatorKind, CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode,
+    Entry, ExceptionSpecification, FloatValue, FunctionAttributes, Linkage, ParseConfig, Position,
+    RefQualifier, Source, StorageClass, TUOptionsBuilder,
 };
 
 #[test]
@@ -12,10 +12,17 @@ fn parse_single_function() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
+    assert_eq!(translation_unit.file_name(), "tests/header.h");
+    assert_eq!(
+        translation_unit.info().arguments,
+        vec!["-x".to_owned(), "c++".to_owned()]
+    );
+    assert_eq!(translation_unit.info().command, None);
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -41,6 +48,21 @@ fn parse_single_function() {
                         cur_type: CursorType::FunctionProto,
                         return_type: CursorType::Int,
                         canonical_return_type: CursorType::Int,
+                        attributes: FunctionAttributes {
+                            is_definition: false,
+                            storage_class: StorageClass::None,
+                            linkage: Linkage::External,
+                            is_inline: false,
+                            is_constexpr: false,
+                            is_explicit: false,
+                            is_const: false,
+                            ref_qualifier: RefQualifier::None,
+                            exception_specification: ExceptionSpecification::None,
+                            is_defaulted: false,
+                            is_deleted: false,
+                            is_override: false,
+                            is_final: false,
+                        }
                     },
                     children: vec![
                         Entry {
@@ -102,6 +124,21 @@ fn parse_single_function() {
                         cur_type: CursorType::FunctionProto,
                         return_type: CursorType::LValueReference,
                         canonical_return_type: CursorType::LValueReference,
+                        attributes: FunctionAttributes {
+                            is_definition: true,
+                            storage_class: StorageClass::None,
+                            linkage: Linkage::External,
+                            is_inline: false,
+                            is_constexpr: false,
+                            is_explicit: false,
+                            is_const: false,
+                            ref_qualifier: RefQualifier::None,
+                            exception_specification: ExceptionSpecification::None,
+                            is_defaulted: false,
+                            is_deleted: false,
+                            is_override: false,
+                            is_final: false,
+                        }
                     },
                     children: vec![
                         Entry {
@@ -138,18 +175,21 @@ fn parse_single_function() {
                             }),
                             children: vec![
                                 Entry {
-                                    current_kind: CursorKind::BinaryOperator(CodeSpan {
-                                        start_pos: Position {
-                                            file_name: "tests/header.h".to_owned(),
-                                            line: 7,
-                                            col: 3,
-                                        },
-                                        end_pos: Position {
-                                            file_name: "tests/header.h".to_owned(),
-                                            line: 7,
-                                            col: 10,
+                                    current_kind: CursorKind::BinaryOperator(
+                                        CodeSpan {
+                                            start_pos: Position {
+                                                file_name: "tests/header.h".to_owned(),
+                                                line: 7,
+                                                col: 3,
+                                            },
+                                            end_pos: Position {
+                                                file_name: "tests/header.h".to_owned(),
+                                                line: 7,
+                                                col: 10,
+                                            },
                                         },
-                                    }),
+                                        BinaryOperatorKind::Assign
+                                    ),
                                     children: vec![
                                         Entry {
                                             current_kind:
@@ -171,18 +211,25 @@ fn parse_single_function() {
                                             children: vec![],
                                         },
                                         Entry {
-                                            current_kind: CursorKind::FloatLiteral(CodeSpan {
-                                                start_pos: Position {
-                                                    file_name: "tests/header.h".to_owned(),
-                                                    line: 7,
-                                                    col: 7,
-                                                },
-                                                end_pos: Position {
-                                                    file_name: "tests/header.h".to_owned(),
-                                                    line: 7,
-                                                    col: 10,
+                                            current_kind: CursorKind::FloatLiteral(
+                                                CodeSpan {
+                                                    start_pos: Position {
+                                                        file_name: "tests/header.h".to_owned(),
+                                                        line: 7,
+                                                        col: 7,
+                                                    },
+                                                    end_pos: Position {
+                                                        file_name: "tests/header.h".to_owned(),
+                                                        line: 7,
+                                                        col: 10,
+                                                    },
                                                 },
-                                            }),
+                                                FloatValue {
+                                                    spelling: ".0f".to_owned(),
+                                                    value: Some(0.0),
+                                                    suffix: "f".to_owned()
+                                                }
+                                            ),
                                             children: vec![],
                                         },
                                     ],
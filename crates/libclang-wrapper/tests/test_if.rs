@@ -1,7 +1,7 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
-    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry, Position,
-    Source, TUOptionsBuilder,
+    BinaryOperatorKind, CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode,
+    Entry, ExceptionSpecification, FunctionAttributes, IntegerValue, Linkage, ParseConfig,
+    Position, RefQualifier, Source, StorageClass, TUOptionsBuilder, UnaryOperatorKind,
 };
 
 #[test]
@@ -12,10 +12,11 @@ fn test_parsing_if_conditions() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -39,7 +40,22 @@ fn test_parsing_if_conditions() {
                     },
                     cur_type: CursorType::FunctionProto,
                     return_type: CursorType::Int,
-                    canonical_return_type: CursorType::Int
+                    canonical_return_type: CursorType::Int,
+                    attributes: FunctionAttributes {
+                        is_definition: true,
+                        storage_class: StorageClass::None,
+                        linkage: Linkage::External,
+                        is_inline: false,
+                        is_constexpr: false,
+                        is_explicit: false,
+                        is_const: false,
+                        ref_qualifier: RefQualifier::None,
+                        exception_specification: ExceptionSpecification::None,
+                        is_defaulted: false,
+                        is_deleted: false,
+                        is_override: false,
+                        is_final: false,
+                    }
                 },
                 children: vec![
                     Entry {
@@ -89,18 +105,21 @@ fn test_parsing_if_conditions() {
                             }),
                             children: vec![
                                 Entry {
-                                    current_kind: CursorKind::BinaryOperator(CodeSpan {
-                                        start_pos: Position {
-                                            file_name: "tests/branching.cpp".to_owned(),
-                                            line: 2,
-                                            col: 7
+                                    current_kind: CursorKind::BinaryOperator(
+                                        CodeSpan {
+                                            start_pos: Position {
+                                                file_name: "tests/branching.cpp".to_owned(),
+                                                line: 2,
+                                                col: 7
+                                            },
+                                            end_pos: Position {
+                                                file_name: "tests/branching.cpp".to_owned(),
+                                                line: 2,
+                                                col: 12
+                                            }
                                         },
-                                        end_pos: Position {
-                                            file_name: "tests/branching.cpp".to_owned(),
-                                            line: 2,
-                                            col: 12
-                                        }
-                                    }),
+                                        BinaryOperatorKind::Greater
+                                    ),
                                     children: vec![
                                         Entry {
                                             current_kind: CursorKind::UnexposedExpression(
@@ -141,18 +160,26 @@ fn test_parsing_if_conditions() {
                                             }]
                                         },
                                         Entry {
-                                            current_kind: CursorKind::IntegerLiteral(CodeSpan {
-                                                start_pos: Position {
-                                                    file_name: "tests/branching.cpp".to_owned(),
-                                                    line: 2,
-                                                    col: 11
+                                            current_kind: CursorKind::IntegerLiteral(
+                                                CodeSpan {
+                                                    start_pos: Position {
+                                                        file_name: "tests/branching.cpp".to_owned(),
+                                                        line: 2,
+                                                        col: 11
+                                                    },
+                                                    end_pos: Position {
+                                                        file_name: "tests/branching.cpp".to_owned(),
+                                                        line: 2,
+                                                        col: 12
+                                                    }
                                                 },
-                                                end_pos: Position {
-                                                    file_name: "tests/branching.cpp".to_owned(),
-                                                    line: 2,
-                                                    col: 12
+                                                IntegerValue {
+                                                    spelling: "0".to_owned(),
+                                                    value: Some(0),
+                                                    radix: 10,
+                                                    suffix: "".to_owned()
                                                 }
-                                            }),
+                                            ),
                                             children: vec![]
                                         }
                                     ]
@@ -184,18 +211,21 @@ fn test_parsing_if_conditions() {
                                             }
                                         }),
                                         children: vec![Entry {
-                                            current_kind: CursorKind::UnaryOperator(CodeSpan {
-                                                start_pos: Position {
-                                                    file_name: "tests/branching.cpp".to_owned(),
-                                                    line: 3,
-                                                    col: 12
+                                            current_kind: CursorKind::UnaryOperator(
+                                                CodeSpan {
+                                                    start_pos: Position {
+                                                        file_name: "tests/branching.cpp".to_owned(),
+                                                        line: 3,
+                                                        col: 12
+                                                    },
+                                                    end_pos: Position {
+                                                        file_name: "tests/branching.cpp".to_owned(),
+                                                        line: 3,
+                                                        col: 14
+                                                    }
                                                 },
-                                                end_pos: Position {
-                                                    file_name: "tests/branching.cpp".to_owned(),
-                                                    line: 3,
-                                                    col: 14
-                                                }
-                                            }),
+                                                UnaryOperatorKind::Minus
+                                            ),
                                             children: vec![Entry {
                                                 current_kind: CursorKind::UnexposedExpression(
                                                     "a".to_owned(),
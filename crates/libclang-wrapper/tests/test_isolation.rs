@@ -0,0 +1,142 @@
+#![cfg(unix)]
+
+use libclang_wrapper::source::{
+    ArgumentsAdjuster, DeclarationFromPHCMode, DiagnosticsMode, Isolation, ParseConfig, Parsed,
+    ParsingError, Source, TUError, TUOptionsBuilder,
+};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+fn script(name: &str, body: &str) -> PathBuf {
+    // unique per test process, so concurrent test runs do not overwrite each other's scripts
+    let path = std::env::temp_dir().join(format!("libclang_wrapper_{}_{}.sh", process::id(), name));
+    fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+fn parse_all(config: &ParseConfig) -> Vec<TUError> {
+    let directory = env!("CARGO_MANIFEST_DIR").to_owned();
+    let commands: Vec<_> = ["class.h", "header.h"]
+        .iter()
+        .map(|file| Parsed {
+            args: vec!["clang++".to_owned(), "-x".to_owned(), "c++".to_owned()],
+            file: format!("{}/tests/{}", directory, file),
+            directory: directory.clone(),
+        })
+        .collect();
+    let source = Source::from_compilation_database(
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        &commands,
+        TUOptionsBuilder::new(),
+        &ArgumentsAdjuster::standard(),
+        config,
+    )
+    .unwrap();
+    source
+        .translation_units
+        .into_iter()
+        .map(|result| result.err().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_crashing_worker() {
+    let mut config = ParseConfig::new();
+    config.isolation(Isolation::Subprocess(script("crash", "kill -SEGV $$")));
+    let errors = parse_all(&config);
+    assert_eq!(errors.len(), 2);
+    for error in errors {
+        assert!(matches!(error.error, ParsingError::Crash(file) if file == error.info.file_name));
+    }
+}
+
+#[test]
+fn test_failing_worker() {
+    let mut config = ParseConfig::new();
+    config.isolation(Isolation::Subprocess(script("fail", "exit 2")));
+    let errors = parse_all(&config);
+    assert_eq!(errors.len(), 2);
+    for error in errors {
+        assert!(matches!(error.error, ParsingError::WorkerFailure(_)));
+    }
+}
+
+#[test]
+fn test_worker_timeout() {
+    let mut config = ParseConfig::new();
+    config
+        .isolation(Isolation::Subprocess(script("hang", "sleep 10")))
+        .timeout(Duration::from_millis(200));
+    let errors = parse_all(&config);
+    assert_eq!(errors.len(), 2);
+    for error in errors {
+        assert!(matches!(error.error, ParsingError::Timeout(_)));
+    }
+}
+
+#[test]
+fn test_missing_worker() {
+    let mut config = ParseConfig::new();
+    config.isolation(Isolation::Subprocess(PathBuf::from("/nonexistent/worker")));
+    let source = Source::from_file(
+        "tests/class.h".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &config,
+    )
+    .unwrap();
+    let error = source.translation_units.into_iter().next().unwrap().err();
+    assert!(matches!(
+        error.unwrap().error,
+        ParsingError::WorkerFailure(_)
+    ));
+}
+
+#[test]
+fn test_parse_worker() {
+    let mut config = ParseConfig::new();
+    config
+        .isolation(Isolation::Subprocess(PathBuf::from(env!(
+            "CARGO_BIN_EXE_parse_worker"
+        ))))
+        .timeout(Duration::from_secs(60));
+    let source = Source::from_file(
+        "tests/class.h".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &config,
+    )
+    .unwrap();
+    let in_process = Source::from_file(
+        "tests/class.h".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &ParseConfig::new(),
+    )
+    .unwrap();
+    let isolated = source.translation_units.into_iter().next().unwrap();
+    let in_process = in_process.translation_units.into_iter().next().unwrap();
+    match (isolated, in_process) {
+        (Ok(isolated), Ok(in_process)) => assert_eq!(isolated.ast(), in_process.ast()),
+        // the worker reports the same problem instead of crashing
+        (Err(isolated), Err(in_process)) => {
+            assert_eq!(isolated.to_string(), in_process.to_string())
+        }
+        (isolated, in_process) => panic!(
+            "isolated: {:?}, in process: {:?}",
+            isolated.err(),
+            in_process.err()
+        ),
+    }
+}
@@ -1,7 +1,7 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
-    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry, Position,
-    Source, TUOptionsBuilder,
+    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry,
+    ExceptionSpecification, FunctionAttributes, IntegerValue, Linkage, ParseConfig, Position,
+    RefQualifier, Source, StorageClass, TUOptionsBuilder,
 };
 
 #[test]
@@ -12,10 +12,11 @@ fn test_parsing_switch_conditions() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -40,8 +41,21 @@ fn test_parsing_switch_conditions() {
                         },
                         cur_type: CursorType::FunctionProto,
                         return_type: CursorType::Int,
-                        canonical_return_type: CursorType::Int
-                    },
+                        canonical_return_type: CursorType::Int, attributes: FunctionAttributes {
+    is_definition: true,
+    storage_class: StorageClass::None,
+    linkage: Linkage::External,
+    is_inline: false,
+    is_constexpr: false,
+    is_explicit: false,
+    is_const: false,
+    ref_qualifier: RefQualifier::None,
+    exception_specification: ExceptionSpecification::None,
+    is_defaulted: false,
+    is_deleted: false,
+    is_override: false,
+    is_final: false,
+}},
                     children: vec![
                         Entry {
                             current_kind: CursorKind::Parameter(
@@ -176,8 +190,7 @@ fn test_parsing_switch_conditions() {
                                                                         line: 3,
                                                                         col: 9
                                                                     }
-                                                                }
-                                                            ),
+                                                                }, IntegerValue { spelling: "1".to_owned(), value: Some(1), radix: 10, suffix: "".to_owned() }),
                                                             children: vec![]
                                                         },
                                                         Entry {
@@ -335,8 +348,7 @@ fn test_parsing_switch_conditions() {
                                                         line: 9,
                                                         col: 11
                                                     }
-                                                }
-                                            ),
+                                                }, IntegerValue { spelling: "0".to_owned(), value: Some(0), radix: 10, suffix: "".to_owned() }),
                                             children: vec![]
                                         }
                                     ]
@@ -0,0 +1,299 @@
+use libclang_wrapper::source::{
+    ArgumentsAdjuster, CursorKind, CursorType, DeclarationChange, DeclarationFromPHCMode,
+    DiagnosticsMode, Entry, ExtractionOptions, KindCategory, ParseConfig, Parsed, Position, Source,
+    TUOptionsBuilder, TranslationUnit, TraversalFilter, UnsavedFile,
+};
+use std::{env, fs};
+
+#[test]
+fn test_live_translation_unit_queries() {
+    let translation_unit = TranslationUnit::parse(
+        "tests/branching.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &ParseConfig::new(),
+    )
+    .unwrap();
+    assert_eq!(translation_unit.file_name(), "tests/branching.cpp");
+
+    let functions = translation_unit.root().children();
+    assert_eq!(functions.len(), 1);
+    let function = functions[0];
+    assert_eq!(function.spelling(), "func");
+    assert_eq!(function.display_name(), "func(int)");
+    assert_eq!(function.cursor_type(), CursorType::FunctionProto);
+    assert_eq!(function.type_spelling(), "int (int)");
+    assert!(!function.is_in_system_header());
+
+    // `a` in `return -a;`
+    let reference = translation_unit
+        .cursor_at(&Position {
+            file_name: "tests/branching.cpp".to_owned(),
+            line: 3,
+            col: 13,
+        })
+        .unwrap();
+    assert_eq!(reference.spelling(), "a");
+    let parameter = reference.referenced().unwrap();
+    assert_eq!(
+        parameter.location(),
+        Position {
+            file_name: "tests/branching.cpp".to_owned(),
+            line: 1,
+            col: 14
+        }
+    );
+    assert_eq!(parameter.semantic_parent(), Some(function));
+    assert_eq!(function.definition(), Some(function));
+    assert!(translation_unit
+        .cursor_at(&Position {
+            file_name: "tests/missing.cpp".to_owned(),
+            line: 1,
+            col: 1,
+        })
+        .is_none());
+
+    let source = Source::from_file(
+        "tests/branching.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &ParseConfig::new(),
+    )
+    .unwrap();
+    let snapshot = source
+        .translation_units
+        .into_iter()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(&translation_unit.ast(), snapshot.ast());
+    assert_eq!(&function.to_entry(), &snapshot.ast().children[0]);
+    assert_eq!(translation_unit.into_tu().ast(), snapshot.ast());
+}
+
+#[test]
+fn test_unsaved_files() {
+    let mut config = ParseConfig::new();
+    config.unsaved_files(vec![
+        UnsavedFile::new("tests/branching.cpp", "int changed();"),
+        UnsavedFile::new(
+            "tests/not_on_disk.cpp",
+            "#include \"branching.cpp\"\nint added();",
+        ),
+    ]);
+    let translation_unit = TranslationUnit::parse(
+        "tests/not_on_disk.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &config,
+    )
+    .unwrap();
+    let functions: Vec<_> = translation_unit
+        .root()
+        .children()
+        .iter()
+        .map(|cursor| cursor.spelling())
+        .collect();
+    assert_eq!(functions, vec!["changed".to_owned(), "added".to_owned()]);
+}
+
+#[test]
+fn test_reparse() {
+    let mut options = TUOptionsBuilder::new();
+    options.precompiled_preamble();
+    let mut config = ParseConfig::new();
+    config.unsaved_files(vec![UnsavedFile::new(
+        "watched.cpp",
+        "namespace kept {}\nnamespace removed {}\nnamespace moved {}",
+    )]);
+    let translation_unit = TranslationUnit::parse(
+        "watched.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Disabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        options,
+        &config,
+    )
+    .unwrap();
+    let reparsed = translation_unit
+        .reparse(&[UnsavedFile::new(
+            "watched.cpp",
+            "namespace kept {}\nnamespace moved {}\nnamespace added {}",
+        )])
+        .unwrap();
+    assert_eq!(reparsed.ast, reparsed.translation_unit.ast());
+    let changes: Vec<_> = reparsed
+        .changes
+        .iter()
+        .map(|change| match change {
+            DeclarationChange::Added(entry) => ("added", &entry.current_kind),
+            DeclarationChange::Removed(entry) => ("removed", &entry.current_kind),
+            DeclarationChange::Modified(_, entry) => ("modified", &entry.current_kind),
+        })
+        .map(|(change, kind)| match kind {
+            CursorKind::Namespace(name, _) => (change, name.as_str()),
+            _ => panic!("unexpected {:?}", kind),
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("modified", "moved"),
+            ("added", "added"),
+            ("removed", "removed")
+        ]
+    );
+}
+
+#[test]
+fn test_save_and_load_ast() {
+    let mut options = TUOptionsBuilder::new();
+    options.for_serialization();
+    let translation_unit = TranslationUnit::parse(
+        "tests/branching.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        options,
+        &ParseConfig::new(),
+    )
+    .unwrap();
+    let ast_file = env::temp_dir().join("libclang_wrapper_branching.ast");
+    translation_unit.save(&ast_file).unwrap();
+    let directory = env!("CARGO_MANIFEST_DIR").to_owned();
+    let commands = vec![
+        Parsed {
+            args: vec!["clang++".to_owned(), "-x".to_owned(), "c++".to_owned()],
+            file: format!("{}/tests/branching.cpp", directory),
+            directory: directory.clone(),
+        },
+        Parsed {
+            args: vec![],
+            file: ast_file.to_string_lossy().into_owned(),
+            directory,
+        },
+    ];
+    let source = Source::from_compilation_database(
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Enabled,
+        &commands,
+        TUOptionsBuilder::new(),
+        &ArgumentsAdjuster::standard(),
+        &ParseConfig::new(),
+    )
+    .unwrap();
+    let translation_units: Vec<_> = source
+        .translation_units
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(translation_units[1].file_name(), ast_file.to_string_lossy());
+    assert_eq!(translation_units[1].ast(), &translation_unit.ast());
+    fs::remove_file(ast_file).unwrap();
+}
+
+#[test]
+fn test_traversal_filter() {
+    let mut filter = TraversalFilter::new();
+    filter.main_file_only();
+    let mut config = ParseConfig::new();
+    config
+        .unsaved_files(vec![
+            UnsavedFile::new("vendor/dependency.h", "int vendored();"),
+            UnsavedFile::new(
+                "main.cpp",
+                "#include \"vendor/dependency.h\"\nint first();\nint second();",
+            ),
+        ])
+        .traversal_filter(filter);
+    let translation_unit = TranslationUnit::parse(
+        "main.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Disabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &config,
+    )
+    .unwrap();
+    assert_eq!(translation_unit.root().children().len(), 3);
+    assert_eq!(translation_unit.ast().children.len(), 2);
+
+    let mut filter = TraversalFilter::new();
+    filter.predicate(|position| position.line != 2);
+    config.traversal_filter(filter);
+    let translation_unit = TranslationUnit::parse(
+        "main.cpp".to_owned(),
+        DeclarationFromPHCMode::Exclude,
+        DiagnosticsMode::Disabled,
+        vec!["-x".to_owned(), "c++".to_owned()],
+        TUOptionsBuilder::new(),
+        &config,
+    )
+    .unwrap();
+    let functions: Vec<_> = translation_unit
+        .root()
+        .children()
+        .into_iter()
+        .filter(|cursor| cursor.is_traversed())
+        .map(|cursor| cursor.spelling())
+        .collect();
+    assert_eq!(functions, vec!["vendored".to_owned(), "second".to_owned()]);
+}
+
+#[test]
+fn test_extraction_options() {
+    let parse = |options: ExtractionOptions| {
+        let mut config = ParseConfig::new();
+        config
+            .unsaved_files(vec![UnsavedFile::new(
+                "api.cpp",
+                "namespace api { int f(int a) { return a + 1; } }",
+            )])
+            .extraction_options(options);
+        TranslationUnit::parse(
+            "api.cpp".to_owned(),
+            DeclarationFromPHCMode::Exclude,
+            DiagnosticsMode::Disabled,
+            vec!["-x".to_owned(), "c++".to_owned()],
+            TUOptionsBuilder::new(),
+            &config,
+        )
+        .unwrap()
+        .ast()
+    };
+    fn depth(entry: &Entry) -> usize {
+        1 + entry.children.iter().map(depth).max().unwrap_or(0)
+    }
+
+    // root, namespace, function, parameter or body, return, addition, ...
+    assert!(depth(&parse(ExtractionOptions::new())) > 5);
+
+    let mut options = ExtractionOptions::new();
+    options.max_depth(1);
+    let ast = parse(options);
+    assert_eq!(depth(&ast), 2);
+
+    let mut options = ExtractionOptions::new();
+    options.stop_at_function_bodies();
+    let ast = parse(options);
+    let function = &ast.children[0].children[0];
+    assert_eq!(function.children.len(), 1);
+    assert!(matches!(
+        &function.children[0].current_kind,
+        CursorKind::Parameter(name, _, _) if name == "a"
+    ));
+
+    let mut options = ExtractionOptions::new();
+    options.declarations_only();
+    assert_eq!(parse(options), ast);
+
+    let mut options = ExtractionOptions::new();
+    options.categories(&[KindCategory::Statement]);
+    assert_eq!(parse(options).children.len(), 0);
+}
@@ -1,7 +1,6 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
     AccessSpecifierType, CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode,
-    Entry, Position, Source, TUOptionsBuilder,
+    Entry, ParseConfig, Position, Source, TUOptionsBuilder,
 };
 
 #[test]
@@ -12,10 +11,11 @@ fn test_type_aliases_and_typedefs() {
         DiagnosticsMode::Enabled,
         vec!["-std=c++14".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -1,7 +1,8 @@
-use libclang_wrapper;
 use libclang_wrapper::source::{
-    CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode, Entry, Position,
-    Source, TUOptionsBuilder,
+    BinaryOperatorKind, CodeSpan, CursorKind, CursorType, DeclarationFromPHCMode, DiagnosticsMode,
+    Entry, ExceptionSpecification, FunctionAttributes, IntegerValue, Linkage, ParseConfig,
+    Position, RefQualifier, Source, StorageClass, TUOptionsBuilder, ThreadLocalKind,
+    UnaryOperatorKind, VariableAttributes,
 };
 
 #[test]
@@ -12,10 +13,11 @@ fn test_while_loop() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -39,7 +41,22 @@ fn test_while_loop() {
                     },
                     cur_type: CursorType::FunctionProto,
                     return_type: CursorType::Void,
-                    canonical_return_type: CursorType::Void
+                    canonical_return_type: CursorType::Void,
+                    attributes: FunctionAttributes {
+                        is_definition: true,
+                        storage_class: StorageClass::None,
+                        linkage: Linkage::External,
+                        is_inline: false,
+                        is_constexpr: false,
+                        is_explicit: false,
+                        is_const: false,
+                        ref_qualifier: RefQualifier::None,
+                        exception_specification: ExceptionSpecification::None,
+                        is_defaulted: false,
+                        is_deleted: false,
+                        is_override: false,
+                        is_final: false,
+                    }
                 },
                 children: vec![Entry {
                     current_kind: CursorKind::CompoundStatement(CodeSpan {
@@ -83,21 +100,37 @@ fn test_while_loop() {
                                             col: 13
                                         }
                                     },
-                                    CursorType::Int
+                                    CursorType::Int,
+                                    VariableAttributes {
+                                        is_definition: true,
+                                        storage_class: StorageClass::None,
+                                        linkage: Linkage::NoLinkage,
+                                        thread_local: ThreadLocalKind::None,
+                                        is_constexpr: false,
+                                        is_const: false,
+                                    }
                                 ),
                                 children: vec![Entry {
-                                    current_kind: CursorKind::IntegerLiteral(CodeSpan {
-                                        start_pos: Position {
-                                            file_name: "tests/while.cpp".to_owned(),
-                                            line: 2,
-                                            col: 11
+                                    current_kind: CursorKind::IntegerLiteral(
+                                        CodeSpan {
+                                            start_pos: Position {
+                                                file_name: "tests/while.cpp".to_owned(),
+                                                line: 2,
+                                                col: 11
+                                            },
+                                            end_pos: Position {
+                                                file_name: "tests/while.cpp".to_owned(),
+                                                line: 2,
+                                                col: 13
+                                            }
                                         },
-                                        end_pos: Position {
-                                            file_name: "tests/while.cpp".to_owned(),
-                                            line: 2,
-                                            col: 13
+                                        IntegerValue {
+                                            spelling: "10".to_owned(),
+                                            value: Some(10),
+                                            radix: 10,
+                                            suffix: "".to_owned()
                                         }
-                                    }),
+                                    ),
                                     children: vec![]
                                 }]
                             }]
@@ -117,18 +150,21 @@ fn test_while_loop() {
                             }),
                             children: vec![
                                 Entry {
-                                    current_kind: CursorKind::BinaryOperator(CodeSpan {
-                                        start_pos: Position {
-                                            file_name: "tests/while.cpp".to_owned(),
-                                            line: 3,
-                                            col: 10
+                                    current_kind: CursorKind::BinaryOperator(
+                                        CodeSpan {
+                                            start_pos: Position {
+                                                file_name: "tests/while.cpp".to_owned(),
+                                                line: 3,
+                                                col: 10
+                                            },
+                                            end_pos: Position {
+                                                file_name: "tests/while.cpp".to_owned(),
+                                                line: 3,
+                                                col: 16
+                                            }
                                         },
-                                        end_pos: Position {
-                                            file_name: "tests/while.cpp".to_owned(),
-                                            line: 3,
-                                            col: 16
-                                        }
-                                    }),
+                                        BinaryOperatorKind::NotEqual
+                                    ),
                                     children: vec![
                                         Entry {
                                             current_kind: CursorKind::UnexposedExpression(
@@ -169,18 +205,26 @@ fn test_while_loop() {
                                             }]
                                         },
                                         Entry {
-                                            current_kind: CursorKind::IntegerLiteral(CodeSpan {
-                                                start_pos: Position {
-                                                    file_name: "tests/while.cpp".to_owned(),
-                                                    line: 3,
-                                                    col: 15
+                                            current_kind: CursorKind::IntegerLiteral(
+                                                CodeSpan {
+                                                    start_pos: Position {
+                                                        file_name: "tests/while.cpp".to_owned(),
+                                                        line: 3,
+                                                        col: 15
+                                                    },
+                                                    end_pos: Position {
+                                                        file_name: "tests/while.cpp".to_owned(),
+                                                        line: 3,
+                                                        col: 16
+                                                    }
                                                 },
-                                                end_pos: Position {
-                                                    file_name: "tests/while.cpp".to_owned(),
-                                                    line: 3,
-                                                    col: 16
+                                                IntegerValue {
+                                                    spelling: "0".to_owned(),
+                                                    value: Some(0),
+                                                    radix: 10,
+                                                    suffix: "".to_owned()
                                                 }
-                                            }),
+                                            ),
                                             children: vec![]
                                         }
                                     ]
@@ -199,18 +243,21 @@ fn test_while_loop() {
                                         }
                                     }),
                                     children: vec![Entry {
-                                        current_kind: CursorKind::UnaryOperator(CodeSpan {
-                                            start_pos: Position {
-                                                file_name: "tests/while.cpp".to_owned(),
-                                                line: 4,
-                                                col: 5
+                                        current_kind: CursorKind::UnaryOperator(
+                                            CodeSpan {
+                                                start_pos: Position {
+                                                    file_name: "tests/while.cpp".to_owned(),
+                                                    line: 4,
+                                                    col: 5
+                                                },
+                                                end_pos: Position {
+                                                    file_name: "tests/while.cpp".to_owned(),
+                                                    line: 4,
+                                                    col: 8
+                                                }
                                             },
-                                            end_pos: Position {
-                                                file_name: "tests/while.cpp".to_owned(),
-                                                line: 4,
-                                                col: 8
-                                            }
-                                        }),
+                                            UnaryOperatorKind::PreDecrement
+                                        ),
                                         children: vec![Entry {
                                             current_kind:
                                                 CursorKind::DeclarationReferenceExpression(
@@ -249,10 +296,11 @@ fn test_do_while_loop() {
         DiagnosticsMode::Enabled,
         vec!["-x".to_owned(), "c++".to_owned()],
         TUOptionsBuilder::new(),
+        &ParseConfig::new(),
     )
     .unwrap();
     let translation_units: Result<Vec<_>, _> = source.translation_units.into_iter().collect();
-    let translation_unit = translation_units.unwrap().into_iter().nth(0).unwrap();
+    let translation_unit = translation_units.unwrap().into_iter().next().unwrap();
     let ast = translation_unit.ast();
     assert_eq!(
         ast,
@@ -276,7 +324,22 @@ fn test_do_while_loop() {
                     },
                     cur_type: CursorType::FunctionProto,
                     return_type: CursorType::Void,
-                    canonical_return_type: CursorType::Void
+                    canonical_return_type: CursorType::Void,
+                    attributes: FunctionAttributes {
+                        is_definition: true,
+                        storage_class: StorageClass::None,
+                        linkage: Linkage::External,
+                        is_inline: false,
+                        is_constexpr: false,
+                        is_explicit: false,
+                        is_const: false,
+                        ref_qualifier: RefQualifier::None,
+                        exception_specification: ExceptionSpecification::None,
+                        is_defaulted: false,
+                        is_deleted: false,
+                        is_override: false,
+                        is_final: false,
+                    }
                 },
                 children: vec![Entry {
                     current_kind: CursorKind::CompoundStatement(CodeSpan {
@@ -321,18 +384,21 @@ fn test_do_while_loop() {
                                 children: vec![]
                             },
                             Entry {
-                                current_kind: CursorKind::BoolLiteral(CodeSpan {
-                                    start_pos: Position {
-                                        file_name: "tests/do_while.cpp".to_owned(),
-                                        line: 3,
-                                        col: 12
+                                current_kind: CursorKind::BoolLiteral(
+                                    CodeSpan {
+                                        start_pos: Position {
+                                            file_name: "tests/do_while.cpp".to_owned(),
+                                            line: 3,
+                                            col: 12
+                                        },
+                                        end_pos: Position {
+                                            file_name: "tests/do_while.cpp".to_owned(),
+                                            line: 3,
+                                            col: 17
+                                        }
                                     },
-                                    end_pos: Position {
-                                        file_name: "tests/do_while.cpp".to_owned(),
-                                        line: 3,
-                                        col: 17
-                                    }
-                                }),
+                                    false
+                                ),
                                 children: vec![]
                             }
                         ]